@@ -1,10 +1,39 @@
 use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 // Return the first existing path given a list of paths as string slices
 fn get_first_path(paths: &[&'static str]) -> Option<&'static str> {
     paths.iter().find(|p| Path::new(p).exists()).copied()
 }
 
+// The date of the build as YYYY-MM-DD, using SOURCE_DATE_EPOCH for reproducible builds
+fn build_date() -> String {
+    let seconds = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.parse::<i64>().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs() as i64)
+                .unwrap_or(0)
+        });
+
+    // convert days since the epoch to a civil date, see
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = seconds.div_euclid(86400) + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
 fn main() {
     let path_zoneinfo: &str = get_first_path(&[
         "/usr/share/zoneinfo",
@@ -28,7 +57,19 @@ fn main() {
 
     println!("cargo:rustc-env=PATH_MAILDIR={path_maildir}");
     println!("cargo:rustc-env=PATH_ZONEINFO={path_zoneinfo}");
+    println!("cargo:rustc-env=SUDO_BUILD_DATE={}", build_date());
+    println!(
+        "cargo:rustc-env=SUDO_BUILD_TARGET={}",
+        std::env::var("TARGET").unwrap_or_default()
+    );
     println!("cargo:rerun-if-changed=build.rs");
+    // the binaries are only rebuilt when their sources change, so refresh the build date then
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=bin");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
 
     println!("cargo:rustc-link-lib=pam");
+    // lets `--version` report PAM support without repeating this decision
+    println!("cargo:rustc-cfg=pam");
+    println!("cargo:rustc-check-cfg=cfg(pam)");
 }
@@ -1,13 +1,16 @@
 pub const USAGE_MSG: &str = "\
-usage: sudo [-u user] [-g group] [-D directory] [-knS] [-i | -s] <command>
+usage: sudo [-u user] [-g group] [-D directory] [-BknS] [-i | -s] <command>
        sudo -h | -K | -k | -V";
 
 const DESCRIPTOR: &str = "sudo - run commands as another user";
 
 const HELP_MSG: &str = "Options:
+  -B, --bell                    ring the terminal bell when authentication fails
+  -C, --close-from=num          close all file descriptors >= num
   -D, --chdir=directory         change the working directory before running command
   -g, --group=group             run command as the specified group name or ID
   -h, --help                    display help message and exit
+  -h, --host=host               list privileges as if running on the specified host (root only)
   -i, --login                   run login shell as the target user; a command may also be
                                 specified
   -K, --remove-timestamp        remove timestamp file completely
@@ -19,6 +22,7 @@ const HELP_MSG: &str = "Options:
   -u, --user=user               run command (or edit file) as specified user name or ID
   -v, --validate                update user's timestamp without running a command
   -V, --version                 display version information and exit
+      --list-defaults           list the available Defaults options and their default values
   --                            stop processing command line arguments";
 
 pub fn long_help_message() -> String {
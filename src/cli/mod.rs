@@ -12,6 +12,7 @@ pub enum SudoAction {
     #[default]
     Help,
     Version,
+    ListDefaults,
     Validate,
     RemoveTimestamp,
     ResetTimestamp,
@@ -23,7 +24,9 @@ pub enum SudoAction {
 #[derive(Debug, Default, PartialEq, Clone)]
 pub struct SudoOptions {
     pub background: bool,
+    pub bell: bool,
     pub chroot: Option<PathBuf>,
+    pub close_from: Option<i32>,
     pub directory: Option<PathBuf>,
     pub group: Option<String>,
     pub host: Option<String>,
@@ -43,6 +46,7 @@ pub struct SudoOptions {
     edit: bool,
     help: bool,
     list: List,
+    list_defaults: bool,
     remove_timestamp: bool,
     pub reset_timestamp: bool,
     validate: bool,
@@ -67,9 +71,10 @@ enum SudoArg {
 }
 
 impl SudoOptions {
-    const TAKES_ARGUMENT_SHORT: &[char] = &['D', 'E', 'g', 'h', 'R', 'U', 'u'];
+    const TAKES_ARGUMENT_SHORT: &[char] = &['C', 'D', 'E', 'g', 'h', 'R', 'U', 'u'];
     const TAKES_ARGUMENT: &[&'static str] = &[
         "chdir",
+        "close-from",
         "preserve-env",
         "group",
         "host",
@@ -179,6 +184,8 @@ impl SudoOptions {
             self.action = SudoAction::Help;
         } else if self.version {
             self.action = SudoAction::Version;
+        } else if self.list_defaults {
+            self.action = SudoAction::ListDefaults;
         } else if self.remove_timestamp {
             self.action = SudoAction::RemoveTimestamp;
         } else if self.reset_timestamp && self.external_args.is_empty() {
@@ -236,6 +243,11 @@ impl SudoOptions {
             }
         }
 
+        // the host can only be overridden when listing privileges
+        if self.host.is_some() && !matches!(self.action, SudoAction::List(_)) {
+            Err("'--host' can only be used together with '--list'")?;
+        }
+
         // check arguments for edit action
         if matches!(self.action, SudoAction::Edit(_))
             && (self.background
@@ -268,6 +280,9 @@ impl SudoOptions {
                     "-b" | "--background" => {
                         options.background = true;
                     }
+                    "-B" | "--bell" => {
+                        options.bell = true;
+                    }
                     "-e" | "--edit" => {
                         options.edit = true;
                     }
@@ -292,6 +307,9 @@ impl SudoOptions {
                         List::Once => options.list = List::Verbose,
                         List::Verbose => {}
                     },
+                    "--list-defaults" => {
+                        options.list_defaults = true;
+                    }
                     "-n" | "--non-interactive" => {
                         options.non_interactive = true;
                     }
@@ -315,6 +333,12 @@ impl SudoOptions {
                     }
                 },
                 SudoArg::Argument(option, value) => match option.as_str() {
+                    "-C" | "--close-from" => {
+                        match value.parse::<i32>() {
+                            Ok(fd) if fd >= 3 => options.close_from = Some(fd),
+                            _ => Err("the argument to '--close-from' must be a number greater than or equal to 3")?,
+                        }
+                    }
                     "-D" | "--chdir" => {
                         options.directory = Some(PathBuf::from(value));
                     }
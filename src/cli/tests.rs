@@ -186,6 +186,15 @@ fn non_interactive() {
     assert!(cmd.non_interactive);
 }
 
+#[test]
+fn bell() {
+    let cmd = SudoOptions::try_parse_from(["sudo", "-B"]).unwrap();
+    assert!(cmd.bell);
+
+    let cmd = SudoOptions::try_parse_from(["sudo", "--bell"]).unwrap();
+    assert!(cmd.bell);
+}
+
 #[test]
 fn preserve_groups() {
     let cmd = SudoOptions::try_parse_from(["sudo", "-P"]).unwrap();
@@ -239,16 +248,28 @@ fn group() {
 
 #[test]
 fn host() {
-    let cmd = SudoOptions::try_parse_from(["sudo", "-hlilo"]).unwrap();
+    let cmd = SudoOptions::try_parse_from(["sudo", "-l", "-hlilo"]).unwrap();
     assert_eq!(cmd.host.as_deref(), Some("lilo"));
 
-    let cmd = SudoOptions::try_parse_from(["sudo", "--host", "lilo"]).unwrap();
+    let cmd = SudoOptions::try_parse_from(["sudo", "-l", "--host", "lilo"]).unwrap();
     assert_eq!(cmd.host.as_deref(), Some("lilo"));
 
-    let cmd = SudoOptions::try_parse_from(["sudo", "--host=lilo"]).unwrap();
+    let cmd = SudoOptions::try_parse_from(["sudo", "-l", "--host=lilo"]).unwrap();
     assert_eq!(cmd.host.as_deref(), Some("lilo"));
 }
 
+#[test]
+fn host_requires_list() {
+    let cmd = SudoOptions::try_parse_from(["sudo", "--host=lilo", "true"]);
+    assert!(cmd.is_err());
+
+    let cmd = SudoOptions::try_parse_from(["sudo", "-v", "--host=lilo"]);
+    assert!(cmd.is_err());
+
+    let cmd = SudoOptions::try_parse_from(["sudo", "-l", "-U", "ferris", "--host=lilo"]).unwrap();
+    assert!(matches!(cmd.action, SudoAction::List(_)));
+}
+
 #[test]
 fn chroot() {
     let cmd = SudoOptions::try_parse_from(["sudo", "-R/some/path"]).unwrap();
@@ -261,6 +282,24 @@ fn chroot() {
     assert_eq!(cmd.chroot, Some(PathBuf::from("/some/path")));
 }
 
+#[test]
+fn close_from() {
+    let cmd = SudoOptions::try_parse_from(["sudo", "-C5", "true"]).unwrap();
+    assert_eq!(cmd.close_from, Some(5));
+
+    let cmd = SudoOptions::try_parse_from(["sudo", "--close-from", "5", "true"]).unwrap();
+    assert_eq!(cmd.close_from, Some(5));
+
+    let cmd = SudoOptions::try_parse_from(["sudo", "--close-from=5", "true"]).unwrap();
+    assert_eq!(cmd.close_from, Some(5));
+
+    let cmd = SudoOptions::try_parse_from(["sudo", "-C", "2", "true"]);
+    assert!(cmd.is_err());
+
+    let cmd = SudoOptions::try_parse_from(["sudo", "-C", "many", "true"]);
+    assert!(cmd.is_err());
+}
+
 #[test]
 fn other_user() {
     let cmd = SudoOptions::try_parse_from(["sudo", "-Uferris"]).unwrap();
@@ -373,6 +412,12 @@ fn validate() {
     assert_eq!(cmd.action, SudoAction::Validate);
 }
 
+#[test]
+fn list_defaults() {
+    let cmd = SudoOptions::try_parse_from(["sudo", "--list-defaults"]).unwrap();
+    assert_eq!(cmd.action, SudoAction::ListDefaults);
+}
+
 #[test]
 fn version() {
     let cmd = SudoOptions::try_parse_from(["sudo", "-V"]).unwrap();
@@ -1,5 +1,8 @@
 use crate::cli::{SudoAction, SudoOptions};
-use crate::system::{hostname, Group, Process, User};
+use crate::log::auth_warn;
+use crate::system::{get_fqdn, get_hostname, get_local_ips, inheritable_fds, Group, Process, User};
+use std::net::IpAddr;
+use std::os::fd::RawFd;
 use std::path::PathBuf;
 
 use super::{
@@ -13,18 +16,22 @@ pub struct Context {
     // cli options
     pub launch: LaunchType,
     pub chdir: Option<PathBuf>,
+    pub preserved_fds: Vec<RawFd>,
     pub command: CommandAndArguments,
     pub target_user: User,
     pub target_group: Group,
     pub stdin: bool,
     pub non_interactive: bool,
+    pub bell: bool,
     pub use_session_records: bool,
     // system
     pub hostname: String,
+    pub local_ips: Vec<IpAddr>,
     pub current_user: User,
     pub process: Process,
     // policy
     pub use_pty: bool,
+    pub command_timeout: Option<u32>,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -35,11 +42,34 @@ pub enum LaunchType {
 }
 
 impl Context {
-    pub fn build_from_options(sudo_options: SudoOptions, path: String) -> Result<Context, Error> {
-        let hostname = hostname();
+    pub fn build_from_options(
+        sudo_options: SudoOptions,
+        path: String,
+        runas_default: &str,
+        fqdn: bool,
+    ) -> Result<Context, Error> {
         let current_user = resolve_current_user()?;
-        let (target_user, target_group) =
-            resolve_target_user_and_group(&sudo_options.user, &sudo_options.group, &current_user)?;
+        // only root is allowed to evaluate the policy on behalf of a different host; the addresses
+        // of that host are unknown, so it can only be matched by name
+        let (hostname, local_ips) = match sudo_options.host.clone() {
+            Some(_) if current_user.uid != 0 => {
+                return Err(Error::Options("only root can use '--host'".to_string()));
+            }
+            Some(host) => (host, Vec::new()),
+            None => {
+                let local_ips = get_local_ips().unwrap_or_else(|err| {
+                    auth_warn!("could not determine the IP addresses of this host: {err}");
+                    Vec::new()
+                });
+                (resolve_hostname(fqdn)?, local_ips)
+            }
+        };
+        let (target_user, target_group) = resolve_target_user_and_group(
+            &sudo_options.user,
+            &sudo_options.group,
+            runas_default,
+            &current_user,
+        )?;
         let (launch, shell) = resolve_launch_and_shell(&sudo_options, &current_user, &target_user);
         let command = match sudo_options.action {
             SudoAction::Run(args) => CommandAndArguments::build_from_args(shell, args, &path),
@@ -56,6 +86,7 @@ impl Context {
 
         Ok(Context {
             hostname,
+            local_ips,
             command,
             current_user,
             target_user,
@@ -63,17 +94,39 @@ impl Context {
             use_session_records: !sudo_options.reset_timestamp,
             launch,
             chdir: sudo_options.directory,
+            // this is determined before sudo opens any file descriptors that are not close-on-exec
+            preserved_fds: sudo_options
+                .close_from
+                .map(inheritable_fds)
+                .transpose()?
+                .unwrap_or_default(),
             stdin: sudo_options.stdin,
             non_interactive: sudo_options.non_interactive,
+            bell: sudo_options.bell,
             process: Process::new(),
             use_pty: true,
+            command_timeout: None,
         })
     }
 }
 
+/// The name of this host, or its fully qualified domain name if `fqdn` is set; this may involve
+/// a DNS query, so it is only done once per invocation
+fn resolve_hostname(fqdn: bool) -> Result<String, Error> {
+    let hostname = get_hostname()?;
+    if !fqdn {
+        return Ok(hostname);
+    }
+
+    Ok(get_fqdn(&hostname).unwrap_or_else(|err| {
+        auth_warn!("could not resolve the fully qualified name of {hostname}: {err}");
+        hostname
+    }))
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{cli::SudoOptions, system::hostname};
+    use crate::{cli::SudoOptions, system::get_hostname};
     use std::collections::HashMap;
 
     use super::Context;
@@ -82,14 +135,15 @@ mod tests {
     fn test_build_context() {
         let options = SudoOptions::try_parse_from(["sudo", "echo", "hello"]).unwrap();
         let path = "/usr/local/sbin:/usr/local/bin:/usr/sbin:/usr/bin:/sbin:/bin";
-        let context = Context::build_from_options(options, path.to_string()).unwrap();
+        let context =
+            Context::build_from_options(options, path.to_string(), "root", false).unwrap();
 
         let mut target_environment = HashMap::new();
         target_environment.insert("SUDO_USER".to_string(), context.current_user.name.clone());
 
         assert_eq!(context.command.command.to_str().unwrap(), "/usr/bin/echo");
         assert_eq!(context.command.arguments, ["hello"]);
-        assert_eq!(context.hostname, hostname());
+        assert_eq!(context.hostname, get_hostname().unwrap());
         assert_eq!(context.target_user.uid, 0);
     }
 }
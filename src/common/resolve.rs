@@ -54,6 +54,7 @@ pub(super) fn resolve_launch_and_shell(
 pub(crate) fn resolve_target_user_and_group(
     target_user_name_or_id: &Option<String>,
     target_group_name_or_id: &Option<String>,
+    runas_default: &str,
     current_user: &User,
 ) -> Result<(User, Group), Error> {
     // resolve user name or #<id> to a user
@@ -83,10 +84,13 @@ pub(crate) fn resolve_target_user_and_group(
                 target_group = Group::from_gid(user.gid)?;
             }
         }
-        // when no -u or -g is specified, default to root:root
+        // when no -u or -g is specified, default to the runas_default user (normally root) and
+        // their primary group
         (None, None) => {
-            target_user = User::from_name("root")?;
-            target_group = Group::from_name("root")?;
+            target_user = User::from_name(runas_default)?;
+            if let Some(user) = &target_user {
+                target_group = Group::from_gid(user.gid)?;
+            }
         }
         _ => {}
     }
@@ -107,7 +111,7 @@ pub(crate) fn resolve_target_user_and_group(
         _ => Err(Error::UserNotFound(
             target_user_name_or_id
                 .as_deref()
-                .unwrap_or_default()
+                .unwrap_or(runas_default)
                 .to_string(),
         )),
     }
@@ -129,7 +133,7 @@ pub(crate) fn is_valid_executable(path: &PathBuf) -> bool {
 /// When resolving a path, this code checks whether the target file is
 /// a regular file and has any executable bits set. It does not specifically
 /// check for user, group, or others' executable bit.
-pub(super) fn resolve_path(command: &Path, path: &str) -> Option<PathBuf> {
+pub(crate) fn resolve_path(command: &Path, path: &str) -> Option<PathBuf> {
     // To prevent command spoofing, sudo checks "." and "" (both denoting current directory)
     // last when searching for a command in the user's PATH (if one or both are in the PATH).
     // Depending on the security policy, the user's PATH environment variable may be modified,
@@ -240,14 +244,21 @@ mod tests {
         let current_user = resolve_current_user().unwrap();
 
         // fallback to root
-        let (user, group) = resolve_target_user_and_group(&None, &None, &current_user).unwrap();
+        let (user, group) =
+            resolve_target_user_and_group(&None, &None, "root", &current_user).unwrap();
         assert_eq!(user.name, "root");
         assert_eq!(group.name, "root");
 
+        // unknown default user
+        let result =
+            resolve_target_user_and_group(&None, &None, "non_existing_ghost", &current_user);
+        assert!(result.is_err());
+
         // unknown user
         let result = resolve_target_user_and_group(
             &Some("non_existing_ghost".to_string()),
             &None,
+            "root",
             &current_user,
         );
         assert!(result.is_err());
@@ -256,13 +267,15 @@ mod tests {
         let result = resolve_target_user_and_group(
             &None,
             &Some("non_existing_ghost".to_string()),
+            "root",
             &current_user,
         );
         assert!(result.is_err());
 
         // fallback to current user when different group specified
         let (user, group) =
-            resolve_target_user_and_group(&None, &Some("root".to_string()), &current_user).unwrap();
+            resolve_target_user_and_group(&None, &Some("root".to_string()), "root", &current_user)
+                .unwrap();
         assert_eq!(user.name, current_user.name);
         assert_eq!(group.name, "root");
 
@@ -270,6 +283,7 @@ mod tests {
         let (user, group) = resolve_target_user_and_group(
             &Some(current_user.name.to_string()),
             &None,
+            "root",
             &current_user,
         )
         .unwrap();
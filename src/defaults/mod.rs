@@ -5,7 +5,7 @@
 pub enum SudoDefault {
     Flag(bool),
     Integer(OptTuple<i64>, fn(&str) -> Option<i64>),
-    Text(OptTuple<Option<&'static str>>),
+    Text(OptTuple<Option<&'static str>>, fn(&str) -> bool),
     List(&'static [&'static str]),
     Enum(OptTuple<StrEnum<'static>>),
 }
@@ -25,16 +25,31 @@ use settings_dsl::*;
 defaults! {
     always_query_group_plugin = false
     always_set_home           = false
+    bell                      = false
     env_reset                 = true
     mail_badpass              = true
+    mail_always               = false
     match_group_by_gid        = false
     use_pty                   = true
     visiblepw                 = false
     env_editor                = true
+    fqdn                      = false
+    rootpw                    = false
+    log_allowed               = true
+    log_denied                = true
+    syslog_pid                = false
+    sudoedit_follow           = false
+    closefrom_override        = false
+    ignore_unknown_defaults   = false
 
     passwd_tries              = 3 [0..=1000]
+    syslog_maxlen             = 0 (!= 0) [0..=1000000]
+    command_timeout           = 0 (!= 0) [0..=2147483647]
 
     secure_path               = None (!= None)
+    env_file                  = None (!= None)
+    runas_default             = "root"
+    editor                    = "/usr/bin/editor" {absolute_paths}
     verifypw                  = "all" (!= "never") [all, always, any, never]
 
     timestamp_timeout         = (15*60) (!= 0) {fractional_minutes}
@@ -53,6 +68,49 @@ defaults! {
                                 "PYTHONINSPECT", "PYTHONUSERBASE", "RUBYLIB", "RUBYOPT", "*=()*"]
 }
 
+/// A description of a Defaults option, as displayed by `sudo --list-defaults`
+pub struct DefaultsEntry {
+    pub name: &'static str,
+    pub kind: &'static str,
+    pub default_value: String,
+}
+
+/// All known Defaults options, in the order in which they are declared
+pub fn defaults_table() -> impl Iterator<Item = DefaultsEntry> {
+    ALL_PARAMS.iter().map(|name| {
+        let (kind, default_value) = match sudo_default(name).expect("internal error") {
+            SudoDefault::Flag(value) => ("boolean", value.to_string()),
+            SudoDefault::Integer(OptTuple { default, .. }, parse) => {
+                ("integer", in_sudoers_units(default, parse))
+            }
+            SudoDefault::Text(OptTuple { default, .. }, _) => (
+                "string",
+                default.map_or("(unset)".to_string(), |text| format!("\"{text}\"")),
+            ),
+            SudoDefault::Enum(OptTuple { default, .. }) => ("string", format!("\"{}\"", &*default)),
+            SudoDefault::List(items) => ("list", format!("\"{}\"", items.join(" "))),
+        };
+
+        DefaultsEntry {
+            name,
+            kind,
+            default_value,
+        }
+    })
+}
+
+/// Integer settings are stored in internal units, which need not be the units in which they are
+/// written in sudoers (e.g. timestamp_timeout is stored in seconds, but written in minutes). The
+/// scale is taken from the parser of the setting itself, so that the two cannot disagree.
+fn in_sudoers_units(value: i64, parse: fn(&str) -> Option<i64>) -> String {
+    let scale = parse("1").filter(|&scale| scale > 0).unwrap_or(1);
+    if value % scale == 0 {
+        (value / scale).to_string()
+    } else {
+        (value as f64 / scale as f64).to_string()
+    }
+}
+
 /// A custom parser to parse seconds as fractional "minutes", the format used by
 /// passwd_timeout and timestamp_timeout.
 fn fractional_minutes(input: &str) -> Option<i64> {
@@ -63,6 +121,12 @@ fn fractional_minutes(input: &str) -> Option<i64> {
     }
 }
 
+/// A custom checker for settings that hold a colon-separated list of absolute paths, such as
+/// "editor".
+fn absolute_paths(input: &str) -> bool {
+    input.split(':').all(|path| path.starts_with('/'))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -89,14 +153,29 @@ mod test {
 
         test! { always_query_group_plugin => Flag(false) };
         test! { always_set_home => Flag(false) };
+        test! { bell => Flag(false) };
         test! { env_reset => Flag(true) };
         test! { mail_badpass => Flag(true) };
+        test! { mail_always => Flag(false) };
         test! { match_group_by_gid => Flag(false) };
         test! { use_pty => Flag(true) };
         test! { visiblepw => Flag(false) };
         test! { env_editor => Flag(true) };
+        test! { fqdn => Flag(false) };
+        test! { rootpw => Flag(false) };
+        test! { log_allowed => Flag(true) };
+        test! { log_denied => Flag(true) };
+        test! { syslog_pid => Flag(false) };
+        test! { sudoedit_follow => Flag(false) };
+        test! { closefrom_override => Flag(false) };
+        test! { ignore_unknown_defaults => Flag(false) };
         test! { passwd_tries => Integer(OptTuple { default: 3, negated: None }, _) };
-        test! { secure_path => Text(OptTuple { default: None, negated: Some(None) }) };
+        test! { syslog_maxlen => Integer(OptTuple { default: 0, negated: Some(0) }, _) };
+        test! { command_timeout => Integer(OptTuple { default: 0, negated: Some(0) }, _) };
+        test! { secure_path => Text(OptTuple { default: None, negated: Some(None) }, _) };
+        test! { env_file => Text(OptTuple { default: None, negated: Some(None) }, _) };
+        test! { runas_default => Text(OptTuple { default: Some("root"), negated: None }, _) };
+        test! { editor => Text(OptTuple { default: Some("/usr/bin/editor"), negated: None }, _) };
         test! { env_keep => List(_) };
         test! { env_check => List(["COLORTERM", "LANG", "LANGUAGE", "LC_*", "LINGUAS", "TERM", "TZ"]) };
         test! { env_delete => List(_) };
@@ -105,4 +184,31 @@ mod test {
         let myenum = StrEnum::new("hello", &["hello", "goodbye"]).unwrap();
         assert!(&myenum as &str == "hello");
     }
+
+    #[test]
+    fn table() {
+        let table = defaults_table().collect::<Vec<_>>();
+        assert_eq!(table.len(), ALL_PARAMS.len());
+
+        let entry = |name: &str| table.iter().find(|entry| entry.name == name).unwrap();
+        let check = |name: &str, kind: &str, default_value: &str| {
+            assert_eq!(entry(name).kind, kind);
+            assert_eq!(entry(name).default_value, default_value);
+        };
+
+        check("env_reset", "boolean", "true");
+        check("passwd_tries", "integer", "3");
+        check("syslog_maxlen", "integer", "0");
+        // written in (fractional) minutes in sudoers
+        check("timestamp_timeout", "integer", "15");
+        assert_eq!(in_sudoers_units(90, fractional_minutes), "1.5");
+        check("secure_path", "string", "(unset)");
+        check("editor", "string", "\"/usr/bin/editor\"");
+        check("verifypw", "string", "\"all\"");
+        check(
+            "env_check",
+            "list",
+            "\"COLORTERM LANG LANGUAGE LC_* LINGUAS TERM TZ\"",
+        );
+    }
 }
@@ -69,8 +69,8 @@ macro_rules! defaults {
         pub fn sudo_default(var: &str) -> Option<SudoDefault> {
             add_from!(Flag, bool);
             add_from!(Integer, i64, negatable, |text| i64::from_str_radix(text, 10).ok());
-            add_from!(Text, &'static str, negatable);
-            add_from!(Text, Option<&'static str>, negatable);
+            add_from!(Text, &'static str, negatable, |_| true);
+            add_from!(Text, Option<&'static str>, negatable, |_| true);
             add_from!(List, &'static [&'static str]);
             add_from!(Enum, StrEnum<'static>, negatable);
 
@@ -89,9 +89,7 @@ macro_rules! defaults {
                               }
                           )?
                           $(
-                              if let SudoDefault::Integer(_, ref mut checker) = &mut result {
-                                  *checker = $fn
-                              }
+                              Checker::install($fn as fn(&str) -> _, &mut result);
                           )?
                           result
                     },
@@ -103,6 +101,27 @@ macro_rules! defaults {
     };
 }
 
+/// A custom function that checks (and for integers also converts) the value of a setting
+pub(super) trait Checker {
+    fn install(self, setting: &mut super::SudoDefault);
+}
+
+impl Checker for fn(&str) -> Option<i64> {
+    fn install(self, setting: &mut super::SudoDefault) {
+        if let super::SudoDefault::Integer(_, ref mut checker) = setting {
+            *checker = self
+        }
+    }
+}
+
+impl Checker for fn(&str) -> bool {
+    fn install(self, setting: &mut super::SudoDefault) {
+        if let super::SudoDefault::Text(_, ref mut checker) = setting {
+            *checker = self
+        }
+    }
+}
+
 pub(super) use add_from;
 pub(super) use defaults;
 pub(super) use optional;
@@ -5,6 +5,7 @@ use std::{
 };
 
 use crate::common::{CommandAndArguments, Context, Environment};
+use crate::log::user_warn;
 use crate::sudoers::Policy;
 use crate::system::PATH_MAX;
 
@@ -174,6 +175,31 @@ fn should_keep(key: &OsStr, value: &OsStr, cfg: &impl Policy) -> bool {
     in_table(key, cfg.env_keep())
 }
 
+/// Parse the contents of an `env_file`: every line is of the form `VARIABLE=value` or
+/// `export VARIABLE=value`, where the value may be enclosed in quotes. Blank lines and
+/// comments are skipped.
+fn parse_env_file(contents: &str) -> impl Iterator<Item = (OsString, OsString)> + '_ {
+    contents.lines().filter_map(|line| {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+
+        let line = line.strip_prefix("export ").unwrap_or(line).trim_start();
+        let (key, value) = line.split_once('=')?;
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            return None;
+        }
+
+        let value = ['"', '\'']
+            .into_iter()
+            .find_map(|quote| value.strip_prefix(quote)?.strip_suffix(quote))
+            .unwrap_or(value);
+
+        Some((key.into(), value.into()))
+    })
+}
+
 /// Construct the final environment from the current one and a sudo context
 /// see <https://github.com/sudo-project/sudo/blob/main/plugins/sudoers/env.c> for the original implementation
 /// see <https://www.sudo.ws/docs/man/sudoers.man/#Command_environment> for the original documentation
@@ -210,12 +236,24 @@ pub fn get_target_environment(
 
     add_extra_env(context, settings, sudo_ps1, &mut environment);
 
+    // the env_file is part of the policy, so it is not filtered; but it doesn't override anything
+    if let Some(path) = settings.env_file() {
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => {
+                for (key, value) in parse_env_file(&contents) {
+                    environment.entry(key).or_insert(value);
+                }
+            }
+            Err(err) => user_warn!("unable to read {path}: {err}"),
+        }
+    }
+
     environment
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{is_safe_tz, should_keep, PATH_ZONEINFO};
+    use super::{is_safe_tz, parse_env_file, should_keep, PATH_ZONEINFO};
     use crate::sudoers::Policy;
     use std::{collections::HashSet, ffi::OsStr};
 
@@ -237,9 +275,37 @@ mod tests {
             None
         }
 
+        fn env_file(&self) -> Option<String> {
+            None
+        }
+
         fn use_pty(&self) -> bool {
             true
         }
+
+        fn syslog_maxlen(&self) -> Option<usize> {
+            None
+        }
+
+        fn log_allowed(&self) -> bool {
+            true
+        }
+
+        fn log_denied(&self) -> bool {
+            true
+        }
+
+        fn mail_always(&self) -> bool {
+            false
+        }
+
+        fn command_timeout(&self) -> Option<u32> {
+            None
+        }
+
+        fn sudoedit_follow(&self) -> bool {
+            false
+        }
     }
 
     #[test]
@@ -294,4 +360,32 @@ mod tests {
             false
         );
     }
+
+    #[test]
+    fn test_env_file() {
+        let contents = "\
+# a comment
+FOO=bar
+
+  export BAZ=\"quoted value\"
+QUUX='single'
+EMPTY=
+not a variable
+=nokey
+";
+        let vars = parse_env_file(contents)
+            .map(|(key, value)| (key.into_string().unwrap(), value.into_string().unwrap()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            vars,
+            [
+                ("FOO", "bar"),
+                ("BAZ", "quoted value"),
+                ("QUUX", "single"),
+                ("EMPTY", ""),
+            ]
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+        );
+    }
 }
@@ -115,6 +115,7 @@ fn create_test_context(sudo_options: &SudoOptions) -> Context {
 
     Context {
         hostname: "test-ubuntu".to_string(),
+        local_ips: Vec::new(),
         command,
         current_user: current_user.clone(),
         target_user: if sudo_options.user.as_deref() == Some("test") {
@@ -131,9 +132,12 @@ fn create_test_context(sudo_options: &SudoOptions) -> Context {
         chdir: sudo_options.directory.clone(),
         stdin: sudo_options.stdin,
         non_interactive: sudo_options.non_interactive,
+        bell: sudo_options.bell,
         process: Process::new(),
         use_session_records: false,
         use_pty: true,
+        command_timeout: None,
+        preserved_fds: Vec::new(),
     }
 }
 
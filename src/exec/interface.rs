@@ -1,4 +1,5 @@
 use std::io::{self, ErrorKind};
+use std::os::fd::RawFd;
 use std::path::PathBuf;
 
 use crate::common::{context::LaunchType, Context};
@@ -9,12 +10,17 @@ pub trait RunOptions {
     fn arguments(&self) -> &Vec<String>;
     fn arg0(&self) -> Option<&PathBuf>;
     fn chdir(&self) -> Option<&PathBuf>;
+    /// Whether to change to the target user's home directory (or `/`) when the current working
+    /// directory is not accessible to them
+    fn cwd_fallback(&self) -> bool;
     fn is_login(&self) -> bool;
     fn user(&self) -> &User;
     fn requesting_user(&self) -> &User;
     fn group(&self) -> &Group;
     fn pid(&self) -> i32;
     fn use_pty(&self) -> bool;
+    fn timeout(&self) -> Option<u32>;
+    fn preserved_fds(&self) -> &[RawFd];
 }
 
 impl RunOptions for Context {
@@ -38,6 +44,10 @@ impl RunOptions for Context {
         self.chdir.as_ref()
     }
 
+    fn cwd_fallback(&self) -> bool {
+        true
+    }
+
     fn is_login(&self) -> bool {
         self.launch == LaunchType::Login
     }
@@ -61,4 +71,12 @@ impl RunOptions for Context {
     fn use_pty(&self) -> bool {
         self.use_pty
     }
+
+    fn timeout(&self) -> Option<u32> {
+        self.command_timeout
+    }
+
+    fn preserved_fds(&self) -> &[RawFd] {
+        &self.preserved_fds
+    }
 }
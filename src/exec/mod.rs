@@ -28,7 +28,7 @@ use crate::{
 use crate::{
     exec::no_pty::exec_no_pty,
     log::dev_info,
-    system::{set_target_user, signal::SignalNumber, term::UserTerm},
+    system::{set_target_user, signal::SignalNumber, term::UserTerm, FileCloser},
 };
 use crate::{log::user_error, system::kill};
 
@@ -106,18 +106,63 @@ fn run_command_internal(options: &impl RunOptions, env: Environment) -> io::Resu
                 Ok(())
             });
         }
+    } else if let Some(current_dir) = options
+        .cwd_fallback()
+        .then(std::env::current_dir)
+        .and_then(Result::ok)
+    {
+        // The target user may not be allowed to access the current working directory (e.g. on a
+        // network filesystem with `root_squash`), fall back to their home directory or `/` then.
+        let fallback_dirs = [options.user().home.as_os_str().as_bytes(), b"/"]
+            .map(|dir| CString::new(dir).expect("nul byte found in directory path"));
+        let c_path = CString::new(current_dir.as_os_str().as_bytes())
+            .expect("nul byte found in current directory path");
+
+        unsafe {
+            command.pre_exec(move || {
+                if let Err(err) = crate::system::chdir(&c_path) {
+                    user_error!(
+                        "unable to change directory to {}: {}",
+                        current_dir.display(),
+                        err
+                    );
+                    if err.kind() == io::ErrorKind::PermissionDenied {
+                        for dir in &fallback_dirs {
+                            if crate::system::chdir(dir).is_ok() {
+                                break;
+                            }
+                        }
+                    }
+                }
+
+                Ok(())
+            });
+        }
+    }
+
+    // file descriptors below the `--close-from` value that were inherited by sudo are passed on
+    // to the command; sudo's own file descriptors are closed regardless of their value.
+    let mut file_closer = FileCloser::new();
+    for fd in options.preserved_fds() {
+        file_closer.except(fd);
     }
 
     if options.use_pty() {
         match UserTerm::open() {
-            Ok(user_tty) => exec_pty(options.pid(), command, user_tty),
+            Ok(user_tty) => exec_pty(
+                options.pid(),
+                command,
+                user_tty,
+                file_closer,
+                options.timeout(),
+            ),
             Err(err) => {
                 dev_info!("Could not open user's terminal, not allocating a pty: {err}");
-                exec_no_pty(options.pid(), command)
+                exec_no_pty(options.pid(), command, file_closer, options.timeout())
             }
         }
     } else {
-        exec_no_pty(options.pid(), command)
+        exec_no_pty(options.pid(), command, file_closer, options.timeout())
     }
 }
 
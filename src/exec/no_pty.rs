@@ -1,7 +1,8 @@
 use std::{
     ffi::c_int,
+    fs::File,
     io::{self, Read, Write},
-    os::unix::{net::UnixStream, process::CommandExt},
+    os::unix::process::CommandExt,
     process::Command,
 };
 
@@ -19,7 +20,7 @@ use crate::{
     exec::{handle_sigchld, opt_fmt, signal_fmt},
     log::{dev_error, dev_info, dev_warn},
     system::{
-        fork, getpgid, getpgrp,
+        alarm, create_pipe, fork, getpgid, getpgrp,
         interface::ProcessId,
         kill, killpg,
         term::{Terminal, UserTerm},
@@ -28,7 +29,12 @@ use crate::{
     },
 };
 
-pub(super) fn exec_no_pty(sudo_pid: ProcessId, mut command: Command) -> io::Result<ProcessOutput> {
+pub(super) fn exec_no_pty(
+    sudo_pid: ProcessId,
+    mut command: Command,
+    mut file_closer: FileCloser,
+    timeout: Option<u32>,
+) -> io::Result<ProcessOutput> {
     // FIXME (ogsudo): Initialize the policy plugin's session here.
 
     // Block all the signals until we are done setting up the signal handlers so we don't miss
@@ -41,12 +47,10 @@ pub(super) fn exec_no_pty(sudo_pid: ProcessId, mut command: Command) -> io::Resu
         }
     };
 
-    let mut file_closer = FileCloser::new();
-
     // FIXME (ogsudo): Some extra config happens here if selinux is available.
 
     // Use a pipe to get the IO error if `exec` fails.
-    let (mut errpipe_tx, errpipe_rx) = UnixStream::pair()?;
+    let (errpipe_rx, mut errpipe_tx) = create_pipe()?;
 
     // Don't close the error pipe as we need it to retrieve the error code if the command execution
     // fails.
@@ -84,6 +88,12 @@ pub(super) fn exec_no_pty(sudo_pid: ProcessId, mut command: Command) -> io::Resu
 
     let mut closure = ExecClosure::new(command_pid, sudo_pid, errpipe_rx, &mut registry)?;
 
+    // The command is terminated when the alarm goes off, this has to happen after the `SIGALRM`
+    // handler has been set up.
+    if let Some(seconds) = timeout {
+        alarm(seconds);
+    }
+
     // Restore the signal mask now that the handlers have been setup.
     if let Some(set) = original_set {
         if let Err(err) = set.set_mask() {
@@ -108,7 +118,7 @@ struct ExecClosure {
     command_pid: Option<ProcessId>,
     sudo_pid: ProcessId,
     parent_pgrp: ProcessId,
-    errpipe_rx: UnixStream,
+    errpipe_rx: File,
     signal_stream: &'static SignalStream,
     signal_handlers: [SignalHandler; ExecClosure::SIGNALS.len()],
 }
@@ -122,7 +132,7 @@ impl ExecClosure {
     fn new(
         command_pid: ProcessId,
         sudo_pid: ProcessId,
-        errpipe_rx: UnixStream,
+        errpipe_rx: File,
         registry: &mut EventRegistry<Self>,
     ) -> io::Result<Self> {
         registry.register_event(&errpipe_rx, PollEvent::Readable, |_| ExecEvent::ErrPipe);
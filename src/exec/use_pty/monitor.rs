@@ -1,7 +1,8 @@
 use std::{
     ffi::c_int,
+    fs::File,
     io::{self, Read, Write},
-    os::unix::{net::UnixStream, process::CommandExt},
+    os::unix::process::CommandExt,
     process::Command,
 };
 
@@ -27,7 +28,7 @@ use crate::{
 use crate::{
     exec::{handle_sigchld, terminate_process, HandleSigchld},
     system::{
-        fork, getpgid, getpgrp,
+        create_pipe, fork, getpgid, getpgrp,
         interface::ProcessId,
         kill, setpgid, setsid,
         term::{PtyFollower, Terminal},
@@ -71,7 +72,7 @@ pub(super) fn exec_monitor(
     })?;
 
     // Use a pipe to get the IO error if `exec_command` fails.
-    let (mut errpipe_tx, errpipe_rx) = UnixStream::pair()?;
+    let (errpipe_rx, mut errpipe_tx) = create_pipe()?;
 
     // Don't close the error pipe as we need it to retrieve the error code if the command execution
     // fails.
@@ -237,7 +238,7 @@ struct MonitorClosure<'a> {
     command_pgrp: ProcessId,
     monitor_pgrp: ProcessId,
     pty_follower: PtyFollower,
-    errpipe_rx: UnixStream,
+    errpipe_rx: File,
     backchannel: &'a mut MonitorBackchannel,
     signal_stream: &'static SignalStream,
     _signal_handlers: [SignalHandler; MonitorClosure::SIGNALS.len()],
@@ -251,7 +252,7 @@ impl<'a> MonitorClosure<'a> {
     fn new(
         command_pid: ProcessId,
         pty_follower: PtyFollower,
-        errpipe_rx: UnixStream,
+        errpipe_rx: File,
         backchannel: &'a mut MonitorBackchannel,
         registry: &mut EventRegistry<Self>,
     ) -> io::Result<Self> {
@@ -22,8 +22,8 @@ use crate::system::signal::{
 };
 use crate::system::term::{Pty, PtyFollower, PtyLeader, TermSize, Terminal, UserTerm};
 use crate::system::wait::WaitOptions;
+use crate::system::{alarm, getpgid, interface::ProcessId};
 use crate::system::{chown, fork, getpgrp, kill, killpg, FileCloser, ForkResult, Group, User};
-use crate::system::{getpgid, interface::ProcessId};
 
 use super::pipe::Pipe;
 use super::{CommandStatus, SIGCONT_BG};
@@ -32,6 +32,8 @@ pub(in crate::exec) fn exec_pty(
     sudo_pid: ProcessId,
     mut command: Command,
     user_tty: UserTerm,
+    mut file_closer: FileCloser,
+    timeout: Option<u32>,
 ) -> io::Result<ProcessOutput> {
     // Allocate a pseudoterminal.
     let pty = get_pty()?;
@@ -59,8 +61,6 @@ pub(in crate::exec) fn exec_pty(
     // Fetch the parent process group so we can signals to it.
     let parent_pgrp = getpgrp();
 
-    let mut file_closer = FileCloser::new();
-
     // Set all the IO streams for the command to the follower side of the pty.
     let mut clone_follower = || -> io::Result<PtyFollower> {
         let follower = pty.follower.try_clone().map_err(|err| {
@@ -225,6 +225,12 @@ pub(in crate::exec) fn exec_pty(
         &mut registry,
     )?;
 
+    // The command is terminated when the alarm goes off, this has to happen after the `SIGALRM`
+    // handler has been set up.
+    if let Some(seconds) = timeout {
+        alarm(seconds);
+    }
+
     // Restore the signal mask now that the handlers have been setup.
     if let Some(set) = original_set {
         if let Err(err) = set.set_mask() {
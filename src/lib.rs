@@ -10,6 +10,7 @@ pub(crate) mod log;
 pub(crate) mod pam;
 pub(crate) mod sudoers;
 pub(crate) mod system;
+pub(crate) mod version;
 
 mod su;
 mod sudo;
@@ -2,6 +2,7 @@ use std::{
     env,
     ffi::OsString,
     fs, io,
+    os::fd::RawFd,
     path::{Path, PathBuf},
 };
 
@@ -221,6 +222,10 @@ impl RunOptions for SuContext {
         None
     }
 
+    fn cwd_fallback(&self) -> bool {
+        false
+    }
+
     fn is_login(&self) -> bool {
         self.options.login
     }
@@ -244,6 +249,14 @@ impl RunOptions for SuContext {
     fn use_pty(&self) -> bool {
         true
     }
+
+    fn timeout(&self) -> Option<u32> {
+        None
+    }
+
+    fn preserved_fds(&self) -> &[RawFd] {
+        &[]
+    }
 }
 
 #[cfg(test)]
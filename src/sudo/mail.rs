@@ -0,0 +1,46 @@
+use std::io::{self, Write};
+use std::os::unix::process::CommandExt;
+use std::process::{Command, Stdio};
+
+const MAILER_PATH: &str = "/usr/sbin/sendmail";
+const MAIL_TO: &str = "root";
+
+/// The environment of the mailer, which runs as root: nothing is inherited from the invoking user
+const MAILER_ENV: [(&str, &str); 4] = [
+    ("HOME", "/"),
+    ("PATH", "/usr/bin:/bin:/usr/sbin:/sbin"),
+    ("LOGNAME", "root"),
+    ("USER", "root"),
+];
+
+/// Notify the administrator about an invocation of sudo by mail. `details` is the same description
+/// of the invocation that is written to the system log.
+pub(super) fn send_mail(hostname: &str, user: &str, details: &str) -> io::Result<()> {
+    let mut mailer = Command::new(MAILER_PATH)
+        .arg("-t")
+        .env_clear()
+        .envs(MAILER_ENV)
+        .current_dir("/")
+        .uid(0)
+        .gid(0)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    if let Some(mut stdin) = mailer.stdin.take() {
+        write!(
+            stdin,
+            "To: {MAIL_TO}\n\
+             From: {MAIL_TO}\n\
+             Auto-Submitted: auto-generated\n\
+             Subject: *** SECURITY information for {hostname} ***\n\
+             \n\
+             {hostname} : {user} : {details}\n"
+        )?;
+    }
+
+    mailer.wait()?;
+
+    Ok(())
+}
@@ -2,10 +2,13 @@
 
 use crate::cli::{help, SudoAction, SudoOptions};
 use crate::common::{resolve::resolve_current_user, Context, Error};
+use crate::defaults::{defaults_table, DefaultsEntry};
 use crate::log::dev_info;
+use crate::sudoers::PreJudgementPolicy;
 use crate::system;
 use crate::system::timestamp::RecordScope;
 use crate::system::{time::Duration, timestamp::SessionRecordFile, Process};
+use crate::version;
 use pam::PamAuthenticator;
 use pipeline::{Pipeline, PolicyPlugin};
 use std::os::unix::fs::MetadataExt;
@@ -13,6 +16,7 @@ use std::path::Path;
 use std::{env, fs};
 
 mod diagnostic;
+mod mail;
 mod pam;
 mod pipeline;
 
@@ -25,7 +29,7 @@ fn candidate_sudoers_file() -> &'static Path {
         pb_rs
     } else {
         dev_info!("Running with /etc/sudoers file");
-        Path::new("/etc/sudoers")
+        Path::new(crate::sudoers::SUDOERS_PATH)
     }
 }
 
@@ -46,6 +50,10 @@ impl PolicyPlugin for SudoersPolicy {
             diagnostic::diagnostic!("{error}", sudoers_path @ pos);
         }
 
+        if sudoers.syslog_pid() {
+            system::syslog_include_pid();
+        }
+
         Ok(sudoers)
     }
 
@@ -57,6 +65,7 @@ impl PolicyPlugin for SudoersPolicy {
         Ok(pre.check(
             &context.current_user,
             &context.hostname,
+            &context.local_ips,
             crate::sudoers::Request {
                 user: &context.target_user,
                 group: &context.target_group,
@@ -87,7 +96,21 @@ fn sudo_process() -> Result<(), Error> {
                 std::process::exit(0);
             }
             SudoAction::Version => {
-                eprintln_ignore_io_error!("sudo-rs {VERSION}");
+                eprintln_ignore_io_error!(
+                    "sudo-rs {VERSION}\n{}",
+                    version::build_info(candidate_sudoers_file())
+                );
+                std::process::exit(0);
+            }
+            SudoAction::ListDefaults => {
+                for DefaultsEntry {
+                    name,
+                    kind,
+                    default_value,
+                } in defaults_table()
+                {
+                    println_ignore_io_error!("{name:<26}{kind:<9}{default_value}");
+                }
                 std::process::exit(0);
             }
             SudoAction::RemoveTimestamp => {
@@ -1,15 +1,18 @@
 use std::collections::HashMap;
 use std::ffi::OsString;
+use std::fs::OpenOptions;
+use std::io::Write;
 
 use crate::common::context::LaunchType;
 use crate::common::{error::Error, Context};
 use crate::log::{dev_info, user_warn};
 use crate::pam::{CLIConverser, Converser, PamContext, PamError, PamErrorType, PamResult};
+use crate::sudoers::AuthenticatingUser;
 use crate::system::term::current_tty_name;
 
 use super::pipeline::AuthPlugin;
 
-type PamBuilder<C> = dyn Fn(&Context) -> PamResult<PamContext<C>>;
+type PamBuilder<C> = dyn Fn(&Context, AuthenticatingUser) -> PamResult<PamContext<C>>;
 
 pub struct PamAuthenticator<C: Converser> {
     builder: Box<PamBuilder<C>>,
@@ -18,7 +21,7 @@ pub struct PamAuthenticator<C: Converser> {
 
 impl<C: Converser> PamAuthenticator<C> {
     fn new(
-        initializer: impl Fn(&Context) -> PamResult<PamContext<C>> + 'static,
+        initializer: impl Fn(&Context, AuthenticatingUser) -> PamResult<PamContext<C>> + 'static,
     ) -> PamAuthenticator<C> {
         PamAuthenticator {
             builder: Box::new(initializer),
@@ -29,13 +32,18 @@ impl<C: Converser> PamAuthenticator<C> {
 
 impl PamAuthenticator<CLIConverser> {
     pub fn new_cli() -> PamAuthenticator<CLIConverser> {
-        PamAuthenticator::new(|context| {
+        PamAuthenticator::new(|context, auth_user| {
+            let auth_user = match auth_user {
+                AuthenticatingUser::InvokingUser => &context.current_user.name,
+                AuthenticatingUser::Root => "root",
+            };
+
             init_pam(
                 matches!(context.launch, LaunchType::Login),
                 matches!(context.launch, LaunchType::Shell),
                 context.stdin,
                 context.non_interactive,
-                &context.current_user.name,
+                auth_user,
                 &context.current_user.name,
             )
         })
@@ -43,18 +51,23 @@ impl PamAuthenticator<CLIConverser> {
 }
 
 impl<C: Converser> AuthPlugin for PamAuthenticator<C> {
-    fn init(&mut self, context: &Context) -> Result<(), Error> {
-        self.pam = Some((self.builder)(context)?);
+    fn init(&mut self, context: &Context, auth_user: AuthenticatingUser) -> Result<(), Error> {
+        self.pam = Some((self.builder)(context, auth_user)?);
         Ok(())
     }
 
-    fn authenticate(&mut self, non_interactive: bool, max_tries: u16) -> Result<(), Error> {
+    fn authenticate(
+        &mut self,
+        non_interactive: bool,
+        bell: bool,
+        max_tries: u16,
+    ) -> Result<(), Error> {
         let pam = self
             .pam
             .as_mut()
             .expect("Pam must be initialized before authenticate");
 
-        attempt_authenticate(pam, non_interactive, max_tries)?;
+        attempt_authenticate(pam, non_interactive, bell, max_tries)?;
 
         Ok(())
     }
@@ -131,6 +144,7 @@ pub fn init_pam(
 pub fn attempt_authenticate<C: Converser>(
     pam: &mut PamContext<C>,
     non_interactive: bool,
+    bell: bool,
     mut max_tries: u16,
 ) -> Result<(), Error> {
     let mut current_try = 0;
@@ -147,6 +161,9 @@ pub fn attempt_authenticate<C: Converser>(
 
             // there was an authentication error, we can retry
             Err(PamError::Pam(PamErrorType::AuthError, _)) => {
+                if bell && !non_interactive {
+                    ring_bell();
+                }
                 max_tries -= 1;
                 if max_tries == 0 {
                     return Err(Error::MaxAuthAttempts(current_try));
@@ -166,3 +183,11 @@ pub fn attempt_authenticate<C: Converser>(
 
     Ok(())
 }
+
+/// Ring the bell on the terminal that the password is prompted for on
+fn ring_bell() {
+    // failing to ring the bell should not affect authentication
+    if let Ok(mut tty) = OpenOptions::new().write(true).open("/dev/tty") {
+        let _ = tty.write_all(b"\x07");
+    }
+}
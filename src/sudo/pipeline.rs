@@ -5,13 +5,16 @@ use crate::cli::SudoOptions;
 use crate::common::{resolve::expand_tilde_in_path, Context, Environment, Error};
 use crate::env::environment;
 use crate::exec::{ExecOutput, ExitReason};
-use crate::log::{auth_info, auth_warn};
+use crate::log::{auth_info, auth_warn, dev_warn};
+use crate::sudo::mail::send_mail;
 use crate::sudo::Duration;
-use crate::sudoers::{Authorization, AuthorizationAllowed, DirChange, Policy, PreJudgementPolicy};
+use crate::sudoers::{
+    AuthenticatingUser, Authorization, AuthorizationAllowed, DirChange, Policy, PreJudgementPolicy,
+};
 use crate::system::interface::UserId;
-use crate::system::term::current_tty_name;
+use crate::system::term::get_tty_name;
 use crate::system::timestamp::{RecordScope, SessionRecordFile, TouchResult};
-use crate::system::{escape_os_str_lossy, Process};
+use crate::system::{escape_os_str_lossy, set_process_name, Process};
 
 mod list;
 
@@ -28,8 +31,13 @@ pub trait PolicyPlugin {
 }
 
 pub trait AuthPlugin {
-    fn init(&mut self, context: &Context) -> Result<(), Error>;
-    fn authenticate(&mut self, non_interactive: bool, max_tries: u16) -> Result<(), Error>;
+    fn init(&mut self, context: &Context, auth_user: AuthenticatingUser) -> Result<(), Error>;
+    fn authenticate(
+        &mut self,
+        non_interactive: bool,
+        bell: bool,
+        max_tries: u16,
+    ) -> Result<(), Error>;
     fn pre_exec(&mut self, target_user: &str) -> Result<Environment, Error>;
     fn cleanup(&mut self);
 }
@@ -49,6 +57,17 @@ impl<Policy: PolicyPlugin, Auth: AuthPlugin> Pipeline<Policy, Auth> {
 
         match authorization {
             Authorization::Forbidden => {
+                if policy.log_denied() {
+                    log_command_denied(&context, policy.syslog_maxlen());
+                }
+                if policy.mail_always() {
+                    mail_command(
+                        &context.hostname,
+                        &context.current_user.name,
+                        command_log_details(&context, policy.syslog_maxlen()),
+                    );
+                }
+
                 return Err(Error::auth(&format!(
                     "I'm sorry {}. I'm afraid I can't do that",
                     context.current_user.name
@@ -56,7 +75,19 @@ impl<Policy: PolicyPlugin, Auth: AuthPlugin> Pipeline<Policy, Auth> {
             }
             Authorization::Allowed(auth) => {
                 self.apply_policy_to_context(&mut context, &policy)?;
-                self.auth_and_update_record_file(&context, auth)?;
+                if let Err(error) = self.auth_and_update_record_file(&context, auth) {
+                    if let Error::MaxAuthAttempts(_) = error {
+                        log_auth_failure(&context, &error, policy.syslog_maxlen());
+                    }
+                    if policy.mail_always() {
+                        mail_command(
+                            &context.hostname,
+                            &context.current_user.name,
+                            command_log_details(&context, policy.syslog_maxlen()),
+                        );
+                    }
+                    return Err(error);
+                }
             }
         }
 
@@ -69,9 +100,24 @@ impl<Policy: PolicyPlugin, Auth: AuthPlugin> Pipeline<Policy, Auth> {
 
         let pid = context.process.pid;
 
+        // the details are gathered now, since the command is moved out of the context below
+        let mail_details = policy
+            .mail_always()
+            .then(|| command_log_details(&context, policy.syslog_maxlen()));
+
         // run command and return corresponding exit code
         let exec_result = if context.command.resolved {
-            log_command_execution(&context);
+            if policy.log_allowed() {
+                log_command_execution(&context, policy.syslog_maxlen());
+            }
+
+            // show what sudo is doing in `ps`
+            if let Some(name) = context.command.command.file_name() {
+                let name = format!("sudo: {}", name.to_string_lossy());
+                if let Err(err) = set_process_name(&name) {
+                    dev_warn!("unable to set process name: {err}");
+                }
+            }
 
             crate::exec::run_command(&context, target_env)
                 .map_err(|io_error| Error::IoError(Some(context.command.command), io_error))
@@ -81,6 +127,11 @@ impl<Policy: PolicyPlugin, Auth: AuthPlugin> Pipeline<Policy, Auth> {
 
         self.authenticator.cleanup();
 
+        // also mail when the command could not be found or executed
+        if let Some(details) = mail_details {
+            mail_command(&context.hostname, &context.current_user.name, details);
+        }
+
         let ExecOutput {
             command_exit_reason,
             restore_signal_handlers,
@@ -123,8 +174,10 @@ impl<Policy: PolicyPlugin, Auth: AuthPlugin> Pipeline<Policy, Auth> {
         context: &Context,
         AuthorizationAllowed {
             must_authenticate,
+            credential,
             prior_validity,
             allowed_attempts,
+            bell,
         }: AuthorizationAllowed,
     ) -> Result<(), Error> {
         let scope = RecordScope::for_process(&Process::new());
@@ -136,10 +189,14 @@ impl<Policy: PolicyPlugin, Auth: AuthPlugin> Pipeline<Policy, Auth> {
             &context.current_user.name,
             prior_validity,
         );
-        self.authenticator.init(context)?;
+        self.authenticator.init(context, credential)?;
         if auth_status.must_authenticate {
-            self.authenticator
-                .authenticate(context.non_interactive, allowed_attempts)?;
+            self.authenticator.authenticate(
+                context.non_interactive,
+                // the bell is part of the password prompt, which is only shown on a terminal
+                (bell || context.bell) && !context.stdin,
+                allowed_attempts,
+            )?;
             if let (Some(record_file), Some(scope)) = (&mut auth_status.record_file, scope) {
                 match record_file.create(scope, context.current_user.uid) {
                     Ok(_) => (),
@@ -183,15 +240,25 @@ impl<Policy: PolicyPlugin, Auth: AuthPlugin> Pipeline<Policy, Auth> {
             context.use_pty = false
         }
 
+        context.command_timeout = policy.command_timeout();
+
         Ok(())
     }
 }
 
 fn build_context(cmd_opts: SudoOptions, pre: &dyn PreJudgementPolicy) -> Result<Context, Error> {
+    // see if the close-from flag is permitted; this is checked before the file descriptors that
+    // it would keep open are looked up
+    if cmd_opts.close_from.is_some() && !pre.closefrom_override() {
+        return Err(Error::Options(
+            "you are not permitted to use the -C option".to_string(),
+        ));
+    }
+
     let secure_path: String = pre
         .secure_path()
         .unwrap_or_else(|| std::env::var("PATH").unwrap_or_default());
-    Context::build_from_options(cmd_opts, secure_path)
+    Context::build_from_options(cmd_opts, secure_path, pre.runas_default(), pre.fqdn())
 }
 
 /// This should determine what the authentication status for the given record
@@ -246,9 +313,39 @@ impl<'a> AuthStatus<'a> {
     }
 }
 
-fn log_command_execution(context: &Context) {
-    let tty_info = if let Ok(tty_name) = current_tty_name() {
-        format!("TTY={} ;", escape_os_str_lossy(&tty_name))
+fn log_command_execution(context: &Context, syslog_maxlen: Option<usize>) {
+    auth_info!(
+        "{} : {}",
+        &context.current_user.name,
+        command_log_details(context, syslog_maxlen)
+    );
+}
+
+fn log_command_denied(context: &Context, syslog_maxlen: Option<usize>) {
+    auth_warn!(
+        "{} : command not allowed ; {}",
+        &context.current_user.name,
+        command_log_details(context, syslog_maxlen)
+    );
+}
+
+fn log_auth_failure(context: &Context, error: &Error, syslog_maxlen: Option<usize>) {
+    auth_warn!(
+        "{} : {error} ; {}",
+        &context.current_user.name,
+        command_log_details(context, syslog_maxlen)
+    );
+}
+
+fn mail_command(hostname: &str, user: &str, details: String) {
+    if let Err(err) = send_mail(hostname, user, &details) {
+        auth_warn!("unable to send mail: {err}");
+    }
+}
+
+fn command_log_details(context: &Context, syslog_maxlen: Option<usize>) -> String {
+    let tty_info = if let Some(tty_name) = get_tty_name(libc::STDIN_FILENO) {
+        format!("TTY={} ;", tty_name.escape_default())
     } else {
         String::from("")
     };
@@ -259,12 +356,12 @@ fn log_command_execution(context: &Context) {
             .unwrap_or_else(|_| OsStr::new("unknown")),
     );
     let user = context.target_user.name.escape_debug().collect::<String>();
-    auth_info!(
-        "{} : {} PWD={} ; USER={} ; COMMAND={}",
-        &context.current_user.name,
-        tty_info,
-        pwd,
-        user,
-        &context.command
-    );
+    let mut command = context.command.to_string();
+    if let Some(maxlen) = syslog_maxlen {
+        if let Some((cutoff, _)) = command.char_indices().nth(maxlen) {
+            command.truncate(cutoff);
+            command.push_str("...");
+        }
+    }
+    format!("{tty_info} PWD={pwd} ; USER={user} ; COMMAND={command}")
 }
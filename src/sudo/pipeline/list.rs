@@ -56,7 +56,8 @@ impl Pipeline<SudoersPolicy, PamAuthenticator<CLIConverser>> {
                 context.hostname
             );
 
-            let matching_entries = sudoers.matching_entries(invoking_user, &context.hostname);
+            let matching_entries =
+                sudoers.matching_entries(invoking_user, &context.hostname, &context.local_ips);
 
             for entry in matching_entries {
                 if verbose_list_mode {
@@ -82,8 +83,12 @@ impl Pipeline<SudoersPolicy, PamAuthenticator<CLIConverser>> {
             target_user: &context.target_user,
             target_group: &context.target_group,
         };
-        let judgement =
-            sudoers.check_list_permission(&context.current_user, &context.hostname, list_request);
+        let judgement = sudoers.check_list_permission(
+            &context.current_user,
+            &context.hostname,
+            &context.local_ips,
+            list_request,
+        );
         match judgement.authorization() {
             Authorization::Allowed(auth) => {
                 self.auth_and_update_record_file(context, auth)?;
@@ -134,7 +139,12 @@ fn check_other_users_list_perms(
         target_user: &context.target_user,
         target_group: &context.target_group,
     };
-    let judgement = sudoers.check_list_permission(other_user, &context.hostname, list_request);
+    let judgement = sudoers.check_list_permission(
+        other_user,
+        &context.hostname,
+        &context.local_ips,
+        list_request,
+    );
 
     if let Authorization::Forbidden = judgement.authorization() {
         return Err(Error::NotAllowed {
@@ -163,7 +173,7 @@ fn check_sudo_command_perms(
         arguments: &context.command.arguments,
     };
 
-    let judgement = sudoers.check(user, &context.hostname, request);
+    let judgement = sudoers.check(user, &context.hostname, &context.local_ips, request);
 
     if let Authorization::Forbidden = judgement.authorization() {
         return Err(Error::Silent);
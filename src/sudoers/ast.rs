@@ -68,6 +68,8 @@ pub enum Authenticate {
 pub struct Tag {
     pub authenticate: Authenticate,
     pub cwd: Option<ChDir>,
+    pub timeout: Option<u32>,
+    pub follow: Option<bool>,
 }
 
 impl Tag {
@@ -107,6 +109,8 @@ pub enum ConfigValue {
     Num(i64),
     List(Mode, Vec<String>),
     Enum(TextEnum),
+    /// a setting that sudo-rs doesn't know about; its value (if any) has been skipped
+    Unknown(Position),
 }
 
 pub enum Mode {
@@ -284,11 +288,18 @@ impl Parse for MetaOrTag {
         let result: Modifier = match keyword.as_str() {
             "PASSWD" => switch(|tag| tag.authenticate = Authenticate::Passwd)?,
             "NOPASSWD" => switch(|tag| tag.authenticate = Authenticate::Nopasswd)?,
+            "FOLLOW" => switch(|tag| tag.follow = Some(true))?,
+            "NOFOLLOW" => switch(|tag| tag.follow = Some(false))?,
             "CWD" => {
                 expect_syntax('=', stream)?;
                 let path: ChDir = expect_nonterminal(stream)?;
                 Box::new(move |tag| tag.cwd = Some(path.clone()))
             }
+            "TIMEOUT" => {
+                expect_syntax('=', stream)?;
+                let Digits(seconds) = expect_nonterminal(stream)?;
+                Box::new(move |tag| tag.timeout = Some(seconds))
+            }
             "ALL" => return make(MetaOrTag(All)),
             alias => return make(MetaOrTag(Alias(alias.to_string()))),
         };
@@ -317,28 +328,6 @@ impl Parse for CommandSpec {
             }
         }
 
-        let start_pos = stream.get_pos();
-        if let Some(Username(keyword)) = try_nonterminal(stream)? {
-            if keyword == "sudoedit" {
-                // note: special behaviour of forward slashes in wildcards, tread carefully
-                unrecoverable!(pos = start_pos, stream, "sudoedit is not yet supported");
-            } else if keyword == "list" {
-                unrecoverable!(pos = start_pos, stream, "list is not yet supported");
-            } else if keyword.starts_with("sha") {
-                unrecoverable!(
-                    pos = start_pos,
-                    stream,
-                    "digest specifications are not supported"
-                )
-            } else {
-                unrecoverable!(
-                    pos = start_pos,
-                    stream,
-                    "expected command but found {keyword}"
-                )
-            };
-        }
-
         let cmd: Spec<Command> = expect_nonterminal(stream)?;
 
         make(CommandSpec(tags, cmd))
@@ -411,8 +400,11 @@ impl Parse for Sudo {
         }
 
         // the existence of "#include" forces us to handle lines that start with #<ID> explicitly
-        if stream.peek() == Some('#') {
-            return if let Ok(ident) = try_nonterminal::<Identifier>(stream) {
+        if accept_if(|c| c == '#', stream).is_ok() {
+            // a '#' that is directly followed by a digit always starts a numerical id
+            return if stream.peek().is_some_and(|c| c.is_ascii_digit()) {
+                let Digits(uid) = expect_nonterminal(stream)?;
+                let ident = Identifier::ID(uid);
                 let first_user = Qualified::Allow(Meta::Only(UserSpecifier::User(ident)));
                 let users = if is_syntax(',', stream)? {
                     // parse the rest of the userlist and add the already-parsed user in front
@@ -426,7 +418,6 @@ impl Parse for Sudo {
                 let permissions = expect_nonterminal(stream)?;
                 make(Sudo::Spec(PermissionSpec { users, permissions }))
             } else {
-                // the failed "try_nonterminal::<Identifier>" will have consumed the '#'
                 // the most ignominious part of sudoers: having to parse bits of comments
                 parse_include(stream).or_else(|_| {
                     while accept_if(|c| c != '\n', stream).is_ok() {}
@@ -607,9 +598,12 @@ impl Parse for (String, ConfigValue) {
             let value = match sudo_default(&name) {
                 Some(Setting::Flag(_)) => ConfigValue::Flag(false),
                 Some(Setting::List(_)) => ConfigValue::List(Mode::Set, vec![]),
-                Some(Setting::Text(OptTuple {
-                    negated: Some(val), ..
-                })) => ConfigValue::Text(val.map(|x| x.into())),
+                Some(Setting::Text(
+                    OptTuple {
+                        negated: Some(val), ..
+                    },
+                    _checker,
+                )) => ConfigValue::Text(val.map(|x| x.into())),
                 Some(Setting::Enum(OptTuple {
                     negated: Some(val), ..
                 })) => ConfigValue::Enum(val),
@@ -619,7 +613,7 @@ impl Parse for (String, ConfigValue) {
                     },
                     _checker,
                 )) => ConfigValue::Num(val),
-                None => unrecoverable!(pos = value_pos, stream, "unknown setting: '{name}'"),
+                None => ConfigValue::Unknown(value_pos..stream.get_pos()),
                 _ => unrecoverable!(
                     pos = value_pos,
                     stream,
@@ -630,7 +624,16 @@ impl Parse for (String, ConfigValue) {
         } else {
             let DefaultName(name) = try_nonterminal(stream)?;
             let Some(cfg) = sudo_default(&name) else {
-                unrecoverable!(pos = id_pos, stream, "unknown setting: '{name}'");
+                // whether this gets reported depends on 'ignore_unknown_defaults', which is
+                // only known after the entire sudoers file has been parsed
+                let pos = id_pos..stream.get_pos();
+                if is_syntax('+', stream)? || is_syntax('-', stream)? {
+                    expect_syntax('=', stream)?;
+                    text_item(stream)?;
+                } else if is_syntax('=', stream)? {
+                    text_item(stream)?;
+                }
+                return make((name, ConfigValue::Unknown(pos)));
             };
 
             if is_syntax('+', stream)? {
@@ -659,8 +662,15 @@ impl Parse for (String, ConfigValue) {
                         let items = parse_vars(stream)?;
                         make((name, ConfigValue::List(Mode::Set, items)))
                     }
-                    Setting::Text(_) => {
+                    Setting::Text(_, checker) => {
                         let text = text_item(stream)?;
+                        if !checker(&text) {
+                            unrecoverable!(
+                                pos = value_pos,
+                                stream,
+                                "'{text}' is not a valid value for {name}"
+                            );
+                        }
                         make((name, ConfigValue::Text(Some(text.into_boxed_str()))))
                     }
                     Setting::Enum(OptTuple { default: key, .. }) => {
@@ -222,6 +222,13 @@ pub trait Token: Sized {
         Self::accept(c)
     }
 
+    /// Some tokens can not be recognised one character at a time; this returns the length of such
+    /// a token at the start of the stream, which is then accepted as a whole before `accept`
+    /// is used to extend it further.
+    fn accept_prefix(_stream: &impl CharStream) -> usize {
+        0
+    }
+
     const ESCAPE: char = '\0';
     fn escaped(_: char) -> bool {
         false
@@ -251,7 +258,14 @@ impl<T: Token> Parse for T {
         }
 
         let start_pos = stream.get_pos();
-        let mut str = accept_escaped::<T>(T::accept_1st, stream)?.to_string();
+        let mut str = String::new();
+        for _ in 0..T::accept_prefix(stream) {
+            str.extend(stream.peek());
+            stream.advance();
+        }
+        if str.is_empty() {
+            str.push(accept_escaped::<T>(T::accept_1st, stream)?);
+        }
         while let Ok(c) = accept_escaped::<T>(T::accept, stream) {
             if str.len() >= T::MAX_LEN {
                 unrecoverable!(stream, "token exceeds maximum length")
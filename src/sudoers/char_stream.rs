@@ -2,6 +2,8 @@ pub trait CharStream {
     fn advance(&mut self);
     fn peek(&mut self) -> Option<char>;
     fn get_pos(&self) -> (usize, usize);
+    /// Look `n` characters ahead without advancing; `peek_nth(0)` is the same as `peek()`
+    fn peek_nth(&self, n: usize) -> Option<char>;
 }
 
 pub struct PeekableWithPos<Iter: Iterator> {
@@ -20,7 +22,7 @@ impl<Iter: Iterator<Item = char>> PeekableWithPos<Iter> {
     }
 }
 
-impl<Iter: Iterator<Item = char>> CharStream for PeekableWithPos<Iter> {
+impl<Iter: Iterator<Item = char> + Clone> CharStream for PeekableWithPos<Iter> {
     fn advance(&mut self) {
         match self.iter.next() {
             Some('\n') => {
@@ -39,10 +41,14 @@ impl<Iter: Iterator<Item = char>> CharStream for PeekableWithPos<Iter> {
     fn get_pos(&self) -> (usize, usize) {
         (self.line, self.col)
     }
+
+    fn peek_nth(&self, n: usize) -> Option<char> {
+        self.iter.clone().nth(n)
+    }
 }
 
 #[cfg(test)]
-impl<Iter: Iterator<Item = char>> CharStream for std::iter::Peekable<Iter> {
+impl<Iter: Iterator<Item = char> + Clone> CharStream for std::iter::Peekable<Iter> {
     fn advance(&mut self) {
         self.next();
     }
@@ -54,6 +60,10 @@ impl<Iter: Iterator<Item = char>> CharStream for std::iter::Peekable<Iter> {
     fn get_pos(&self) -> (usize, usize) {
         (0, 0)
     }
+
+    fn peek_nth(&self, n: usize) -> Option<char> {
+        self.clone().nth(n)
+    }
 }
 
 #[cfg(test)]
@@ -69,6 +79,9 @@ mod test {
         stream.advance();
         stream.advance();
         assert_eq!(stream.peek(), Some('3'));
+        assert_eq!(stream.peek_nth(0), Some('3'));
+        assert_eq!(stream.peek_nth(1), Some('\n'));
+        assert_eq!(stream.peek_nth(2), None);
         stream.advance();
         assert_eq!(stream.get_pos(), (2, 2));
     }
@@ -17,16 +17,22 @@ mod verbose;
 pub struct Entry<'a> {
     run_as: &'a RunAs,
     cmd_specs: Vec<(Tag, Qualified<&'a Meta<Command>>)>,
+    runas_default: &'a str,
 }
 
 impl<'a> Entry<'a> {
     pub(super) fn new(
         run_as: &'a RunAs,
         cmd_specs: Vec<(Tag, Qualified<&'a Meta<Command>>)>,
+        runas_default: &'a str,
     ) -> Self {
         debug_assert!(!cmd_specs.is_empty());
 
-        Self { run_as, cmd_specs }
+        Self {
+            run_as,
+            cmd_specs,
+            runas_default,
+        }
     }
 
     pub fn verbose(self) -> impl fmt::Display + 'a {
@@ -36,10 +42,14 @@ impl<'a> Entry<'a> {
 
 impl fmt::Display for Entry<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let Self { run_as, cmd_specs } = self;
+        let Self {
+            run_as,
+            cmd_specs,
+            runas_default,
+        } = self;
 
         f.write_str("    (")?;
-        write_users(run_as, f)?;
+        write_users(run_as, runas_default, f)?;
         if !run_as.groups.is_empty() {
             f.write_str(" : ")?;
         }
@@ -63,10 +73,13 @@ impl fmt::Display for Entry<'_> {
     }
 }
 
-fn write_users(run_as: &RunAs, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+fn write_users(
+    run_as: &RunAs,
+    runas_default: &str,
+    f: &mut fmt::Formatter<'_>,
+) -> Result<(), fmt::Error> {
     if run_as.users.is_empty() {
-        // XXX assumes that the superuser is called "root"
-        f.write_str("root")?;
+        f.write_str(runas_default)?;
     }
 
     let mut is_first_user = true;
@@ -11,7 +11,11 @@ pub struct Verbose<'a>(pub Entry<'a>);
 
 impl fmt::Display for Verbose<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let Self(Entry { run_as, cmd_specs }) = self;
+        let Self(Entry {
+            run_as,
+            cmd_specs,
+            runas_default,
+        }) = self;
 
         let mut last_tag = None;
         for (tag, cmd_spec) in cmd_specs {
@@ -21,7 +25,7 @@ impl fmt::Display for Verbose<'_> {
                     f.write_str("\n")?;
                 }
 
-                write_entry_header(run_as, f)?;
+                write_entry_header(run_as, runas_default, f)?;
                 write_tag(f, tag)?;
                 f.write_str("\n    Commands:")?;
             }
@@ -35,16 +39,20 @@ impl fmt::Display for Verbose<'_> {
     }
 }
 
-fn write_entry_header(run_as: &RunAs, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+fn write_entry_header(
+    run_as: &RunAs,
+    runas_default: &str,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
     f.write_str("\nSudoers entry:")?;
 
-    write_users(run_as, f)?;
+    write_users(run_as, runas_default, f)?;
     write_groups(run_as, f)
 }
 
-fn write_users(run_as: &RunAs, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+fn write_users(run_as: &RunAs, runas_default: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     f.write_str("\n    RunAsUsers: ")?;
-    super::write_users(run_as, f)
+    super::write_users(run_as, runas_default, f)
 }
 
 fn write_groups(run_as: &RunAs, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -10,9 +10,11 @@ mod entry;
 mod tokens;
 
 use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
 use std::path::{Path, PathBuf};
 use std::{io, mem};
 
+use crate::common::resolve::{canonicalize, resolve_path};
 use crate::log::auth_warn;
 use crate::system::can_execute;
 use crate::system::interface::{UnixGroup, UnixUser};
@@ -22,6 +24,9 @@ use tokens::*;
 /// How many nested include files do we allow?
 const INCLUDE_LIMIT: u8 = 128;
 
+/// The location of the system sudoers file
+pub const SUDOERS_PATH: &str = "/etc/sudoers";
+
 /// Export some necessary symbols from modules
 pub use ast::TextEnum;
 pub struct Error(pub Option<basic_parser::Position>, pub String);
@@ -54,7 +59,9 @@ pub struct Judgement {
 
 mod policy;
 
-pub use policy::{Authorization, AuthorizationAllowed, DirChange, Policy, PreJudgementPolicy};
+pub use policy::{
+    AuthenticatingUser, Authorization, AuthorizationAllowed, DirChange, Policy, PreJudgementPolicy,
+};
 
 pub use self::entry::Entry;
 
@@ -77,13 +84,14 @@ impl Sudoers {
         &self,
         am_user: &User,
         on_host: &str,
+        local_ips: &[IpAddr],
         request: Request<User, Group>,
     ) -> Judgement {
         // exception: if user is root or does not switch users, NOPASSWD is implied
         let skip_passwd =
             am_user.is_root() || (request.user == am_user && in_group(am_user, request.group));
 
-        let mut flags = check_permission(self, am_user, on_host, request);
+        let mut flags = check_permission(self, am_user, on_host, local_ips, request);
         if let Some(Tag { authenticate, .. }) = flags.as_mut() {
             if skip_passwd {
                 *authenticate = Authenticate::Nopasswd;
@@ -100,6 +108,7 @@ impl Sudoers {
         &self,
         invoking_user: &User,
         hostname: &str,
+        local_ips: &[IpAddr],
         request: ListRequest<User, Group>,
     ) -> Judgement {
         // exception: if user is root or does not switch users, NOPASSWD is implied
@@ -108,7 +117,7 @@ impl Sudoers {
                 && in_group(invoking_user, request.target_group));
 
         let mut flags = self
-            .matching_user_specs(invoking_user, hostname)
+            .matching_user_specs(invoking_user, hostname, local_ips)
             .flatten()
             .fold(None::<Tag>, |outcome, (_, (tag, _))| {
                 if let Some(outcome) = outcome {
@@ -132,7 +141,7 @@ impl Sudoers {
         }
     }
 
-    /// returns `User_Spec`s that match `invoking_user` and `hostname`
+    /// returns `User_Spec`s that match `invoking_user` and `hostname` (or one of `local_ips`)
     ///
     /// it also distributes `Tag_Spec`s across the `Cmnd_Spec` list of each `User_Spec`
     ///
@@ -142,11 +151,17 @@ impl Sudoers {
         &'a self,
         invoking_user: &'b User,
         hostname: &'c str,
+        local_ips: &'c [IpAddr],
     ) -> impl Iterator<Item = impl Iterator<Item = (Option<&'a RunAs>, (Tag, &'a Spec<Command>))> + 'b>
            + 'c {
-        let Self { rules, aliases, .. } = self;
+        let Self {
+            rules,
+            aliases,
+            settings,
+        } = self;
+        let fqdn = settings.flags.contains("fqdn");
         let user_aliases = get_aliases(&aliases.user, &match_user(invoking_user));
-        let host_aliases = get_aliases(&aliases.host, &match_token(hostname));
+        let host_aliases = get_aliases(&aliases.host, &match_host(hostname, fqdn, local_ips));
 
         rules
             .iter()
@@ -156,7 +171,7 @@ impl Sudoers {
             })
             .flatten()
             .filter_map(move |(hosts, runas_cmds)| {
-                find_item(hosts, &match_token(hostname), &host_aliases)?;
+                find_item(hosts, &match_host(hostname, fqdn, local_ips), &host_aliases)?;
                 Some(distribute_tags(runas_cmds))
             })
     }
@@ -166,33 +181,58 @@ impl Sudoers {
         &'a self,
         invoking_user: &User,
         hostname: &str,
+        local_ips: &[IpAddr],
     ) -> Vec<Entry<'a>> {
         // NOTE this method MUST NOT perform any filtering that `Self::check` does not do to
         // ensure `sudo $command` and `sudo --list` use the same permission checking logic
-        let user_specs = self.matching_user_specs(invoking_user, hostname);
+        let user_specs = self.matching_user_specs(invoking_user, hostname, local_ips);
 
         let cmnd_aliases = unfold_alias_table(&self.aliases.cmnd);
+        let runas_default = self.runas_default();
         let mut entries = vec![];
         for cmd_specs in user_specs {
-            group_cmd_specs_per_runas(cmd_specs, &mut entries, &cmnd_aliases);
+            group_cmd_specs_per_runas(cmd_specs, &mut entries, &cmnd_aliases, runas_default);
         }
 
         entries
     }
 
-    pub(crate) fn solve_editor_path(&self) -> Option<PathBuf> {
-        if self.settings.flags.contains("env_editor") {
-            for key in ["SUDO_EDITOR", "VISUAL", "EDITOR"] {
-                if let Some(var) = std::env::var_os(key) {
-                    let path = Path::new(&var);
-                    if can_execute(path) {
-                        return Some(path.to_owned());
-                    }
+    /// The user that commands are run as if no user is specified on the command line
+    pub(crate) fn runas_default(&self) -> &str {
+        self.settings.str_value["runas_default"]
+            .as_deref()
+            .unwrap_or("root")
+    }
+
+    /// Determine the editor to be used by visudo: if `env_editor` is set, the editor specified
+    /// in the environment is used; otherwise it is only used if it appears in the `editor` list.
+    /// If that fails, the first executable editor in the `editor` list is used.
+    pub(crate) fn solve_editor_path(&self) -> io::Result<PathBuf> {
+        let editor_list = self.settings.str_value["editor"]
+            .as_deref()
+            .unwrap_or_default();
+        let editors = editor_list.split(':').map(Path::new).collect::<Vec<_>>();
+
+        for key in ["SUDO_EDITOR", "VISUAL", "EDITOR"] {
+            if let Some(var) = std::env::var_os(key) {
+                let path = Path::new(&var);
+                let allowed = self.settings.flags.contains("env_editor") || editors.contains(&path);
+                if allowed && can_execute(path) {
+                    return Ok(path.to_owned());
                 }
             }
         }
 
-        None
+        editors
+            .into_iter()
+            .find(|path| path.is_file() && can_execute(path))
+            .map(Path::to_owned)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("no editor found (editor path = {editor_list})"),
+                )
+            })
     }
 }
 
@@ -200,6 +240,7 @@ fn group_cmd_specs_per_runas<'a>(
     cmnd_specs: impl Iterator<Item = (Option<&'a RunAs>, (Tag, &'a Spec<Command>))>,
     entries: &mut Vec<Entry<'a>>,
     cmnd_aliases: &HashMap<&String, &'a Vec<Spec<Command>>>,
+    runas_default: &'a str,
 ) {
     static EMPTY_RUNAS: RunAs = RunAs {
         users: Vec::new(),
@@ -215,6 +256,7 @@ fn group_cmd_specs_per_runas<'a>(
                 entries.push(Entry::new(
                     runas.take().unwrap_or(&EMPTY_RUNAS),
                     mem::take(&mut collected_specs),
+                    runas_default,
                 ));
             }
 
@@ -241,7 +283,11 @@ fn group_cmd_specs_per_runas<'a>(
     }
 
     if !collected_specs.is_empty() {
-        entries.push(Entry::new(runas.unwrap_or(&EMPTY_RUNAS), collected_specs));
+        entries.push(Entry::new(
+            runas.unwrap_or(&EMPTY_RUNAS),
+            collected_specs,
+            runas_default,
+        ));
     }
 }
 
@@ -292,18 +338,22 @@ fn check_permission<User: UnixUser + PartialEq<User>, Group: UnixGroup>(
     sudoers: &Sudoers,
     am_user: &User,
     on_host: &str,
+    local_ips: &[IpAddr],
     request: Request<User, Group>,
 ) -> Option<Tag> {
     let cmdline = (request.command, request.arguments);
+    let secure_path = sudoers.settings.str_value["secure_path"].as_deref();
 
     let aliases = &sudoers.aliases;
-    let cmnd_aliases = get_aliases(&aliases.cmnd, &match_command(cmdline));
+    let cmnd_aliases = get_aliases(&aliases.cmnd, &match_command(cmdline, secure_path));
     let runas_user_aliases = get_aliases(&aliases.runas, &match_user(request.user));
     let runas_group_aliases = get_aliases(&aliases.runas, &match_group_alias(request.group));
 
     // NOTE to ensure `sudo $command` and `sudo --list` behave the same, both this function and
     // `Sudoers::matching_entries` must call this `matching_user_specs` method
-    let matching_user_specs = sudoers.matching_user_specs(am_user, on_host).flatten();
+    let matching_user_specs = sudoers
+        .matching_user_specs(am_user, on_host, local_ips)
+        .flatten();
 
     let allowed_commands = matching_user_specs.filter_map(|(runas, cmdspec)| {
         if let Some(RunAs { users, groups }) = runas {
@@ -314,14 +364,20 @@ fn check_permission<User: UnixUser + PartialEq<User>, Group: UnixGroup>(
             if !stays_in_group {
                 find_item(groups, &match_group(request.group), &runas_group_aliases)?
             }
-        } else if !(request.user.is_root() && in_group(request.user, request.group)) {
+        } else if !(request.user.has_name(sudoers.runas_default())
+            && in_group(request.user, request.group))
+        {
             None?;
         }
 
         Some(cmdspec)
     });
 
-    find_item(allowed_commands, &match_command(cmdline), &cmnd_aliases)
+    find_item(
+        allowed_commands,
+        &match_command(cmdline, secure_path),
+        &cmnd_aliases,
+    )
 }
 
 /// Process a raw parsed AST bit of RunAs + Command specifications:
@@ -452,20 +508,61 @@ fn match_group_alias(group: &impl UnixGroup) -> impl Fn(&UserSpecifier) -> bool
     }
 }
 
-fn match_token<T: basic_parser::Token + std::ops::Deref<Target = String>>(
-    text: &str,
-) -> (impl Fn(&T) -> bool + '_) {
-    move |token| token.as_str() == text
+/// Without `fqdn`, a host pattern that has no domain part is compared against the hostname with
+/// its domain stripped; patterns that do mention a domain are always compared in full. Patterns
+/// that specify an IP address or network match if any of the `local_ips` falls inside of it.
+fn match_host<'a>(
+    hostname: &'a str,
+    fqdn: bool,
+    local_ips: &'a [IpAddr],
+) -> impl Fn(&Hostname) -> bool + 'a {
+    let short_hostname = hostname.split_once('.').map_or(hostname, |(host, _)| host);
+    move |pattern| {
+        if let Some(network) = pattern.ip_network() {
+            local_ips.iter().any(|addr| in_network(addr, network))
+        } else if fqdn || pattern.contains('.') {
+            pattern.as_str() == hostname
+        } else {
+            pattern.as_str() == short_hostname
+        }
+    }
+}
+
+fn in_network(addr: &IpAddr, (network, prefix): (IpAddr, u32)) -> bool {
+    match (addr, network) {
+        (IpAddr::V4(addr), IpAddr::V4(network)) => {
+            let mask = u32::MAX.checked_shl(32 - prefix).unwrap_or(0);
+            u32::from(*addr) & mask == u32::from(network) & mask
+        }
+        (IpAddr::V6(addr), IpAddr::V6(network)) => {
+            let mask = u128::MAX.checked_shl(128 - prefix).unwrap_or(0);
+            u128::from(*addr) & mask == u128::from(network) & mask
+        }
+        _ => false,
+    }
 }
 
-fn match_command<'a>((cmd, args): (&'a Path, &'a [String])) -> (impl Fn(&Command) -> bool + 'a) {
+fn match_command<'a>(
+    (cmd, args): (&'a Path, &'a [String]),
+    secure_path: Option<&'a str>,
+) -> impl Fn(&Command) -> bool + 'a {
     let opts = glob::MatchOptions {
         require_literal_separator: true,
         ..glob::MatchOptions::new()
     };
     move |(cmdpat, argpat)| {
-        cmdpat.matches_path_with(cmd, opts)
-            && argpat.as_ref().map_or(true, |vec| args == vec.as_ref())
+        let matches_cmd = if cmdpat.as_str().starts_with('/') {
+            cmdpat.matches_path_with(cmd, opts)
+        } else {
+            // relative commands only survive analysis if secure_path is set; look them up in the
+            // same way the command that is being requested was looked up
+            secure_path
+                .and_then(|path| resolve_path(Path::new(cmdpat.as_str()), path))
+                .and_then(|path| canonicalize(path).ok())
+                .is_some_and(|path| path == cmd)
+        };
+
+        matches_cmd && argpat.as_ref().map_or(true, |vec| args == vec.as_ref())
     }
 }
 
@@ -533,7 +630,7 @@ impl Default for Settings {
                         this.flags.insert(key.to_string());
                     }
                 }
-                SudoDefault::Text(OptTuple { default, .. }) => {
+                SudoDefault::Text(OptTuple { default, .. }, _) => {
                     this.str_value
                         .insert(key.to_string(), default.map(|x| x.into()));
                 }
@@ -614,6 +711,20 @@ fn analyze(
             diagnostics: &mut Vec<Error>,
             safety_count: &mut u8,
         ) {
+            let sudoers = sudoers.into_iter().collect::<Vec<_>>();
+
+            // 'ignore_unknown_defaults' must be in effect before any other Defaults are processed
+            for item in &sudoers {
+                let Ok(Sudo::Decl(Defaults(params))) = item else {
+                    continue;
+                };
+                for (name, value) in params {
+                    if let ("ignore_unknown_defaults", Flag(value)) = (name.as_str(), value) {
+                        self.set_default(name.clone(), Flag(*value))
+                    }
+                }
+            }
+
             for item in sudoers {
                 match item {
                     Ok(line) => match line {
@@ -628,7 +739,16 @@ fn analyze(
 
                         Sudo::Decl(Defaults(params)) => {
                             for (name, value) in params {
-                                self.set_default(name, value)
+                                if let Unknown(pos) = value {
+                                    if !self.settings.flags.contains("ignore_unknown_defaults") {
+                                        diagnostics.push(Error(
+                                            Some(pos),
+                                            format!("unknown setting: '{name}'"),
+                                        ))
+                                    }
+                                } else {
+                                    self.set_default(name, value)
+                                }
                             }
                         }
 
@@ -703,6 +823,8 @@ fn analyze(
                 Num(value) => {
                     self.settings.int_value.insert(name, value);
                 }
+                // unknown settings are reported (or ignored) when they are encountered
+                Unknown(_) => {}
             }
         }
     }
@@ -710,6 +832,10 @@ fn analyze(
     let mut diagnostics = vec![];
     result.process(path, sudoers, &mut diagnostics, &mut 0);
 
+    if result.settings.str_value["secure_path"].is_none() {
+        drop_relative_commands(&mut result, &mut diagnostics);
+    }
+
     let alias = &mut result.aliases;
     alias.user.0 = sanitize_alias_table(&alias.user.1, &mut diagnostics);
     alias.host.0 = sanitize_alias_table(&alias.host.1, &mut diagnostics);
@@ -719,6 +845,65 @@ fn analyze(
     (result, diagnostics)
 }
 
+/// Commands in a sudoers file must be specified using an absolute path; the only exception to this
+/// is when a `secure_path` has been set, in which case a command name is looked up in it when
+/// matching a request. If it is not set, rules and aliases that rely on relative commands are
+/// dropped, as if they contained a syntax error; this includes rules that refer to such aliases.
+fn drop_relative_commands(sudoers: &mut Sudoers, diagnostics: &mut Vec<Error>) {
+    // explain why a command specification cannot be used, if that is the case
+    fn relative(spec: &Spec<Command>, dropped: &HashSet<String>) -> Option<String> {
+        let (Qualified::Allow(meta) | Qualified::Forbid(meta)) = spec;
+        match meta {
+            Meta::Only((cmd, _)) if !cmd.as_str().starts_with('/') => Some(format!(
+                "command '{cmd}' is not specified using an absolute path"
+            )),
+            Meta::Alias(name) if dropped.contains(name) => {
+                Some(format!("Cmnd_Alias '{name}' was ignored"))
+            }
+            _ => None,
+        }
+    }
+
+    // aliases can refer to other aliases in any order, so keep going until nothing changes
+    let mut dropped = HashSet::new();
+    loop {
+        let count = dropped.len();
+        for Def(name, specs) in &sudoers.aliases.cmnd.1 {
+            if dropped.contains(name) {
+                continue;
+            }
+            if let Some(reason) = specs.iter().find_map(|spec| relative(spec, &dropped)) {
+                diagnostics.push(Error(
+                    None,
+                    format!("ignoring Cmnd_Alias '{name}': {reason}"),
+                ));
+                dropped.insert(name.clone());
+            }
+        }
+        if dropped.len() == count {
+            break;
+        }
+    }
+
+    sudoers
+        .aliases
+        .cmnd
+        .1
+        .retain(|Def(name, _)| !dropped.contains(name));
+    sudoers.rules.retain(|rule| {
+        let reason = rule
+            .permissions
+            .iter()
+            .flat_map(|(_, cmds)| cmds)
+            .find_map(|(_, CommandSpec(_, spec))| relative(spec, &dropped));
+        if let Some(reason) = &reason {
+            diagnostics.push(Error(None, format!("ignoring rule: {reason}")));
+        }
+
+        reason.is_none()
+    });
+}
+
 /// Alias definition inin a Sudoers file can come in any order; and aliases can refer to other aliases, etc.
 /// It is much easier if they are presented in a "definitional order" (i.e. aliases that use other aliases occur later)
 /// At the same time, this is a good place to detect problems in the aliases, such as unknown aliases and cycles.
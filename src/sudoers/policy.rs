@@ -1,6 +1,7 @@
 use super::Sudoers;
 
 use super::Judgement;
+use super::Settings;
 use crate::system::time::Duration;
 /// Data types and traits that represent what the "terms and conditions" are after a succesful
 /// permission check.
@@ -24,7 +25,30 @@ pub trait Policy {
 
     fn secure_path(&self) -> Option<String>;
 
+    /// A file containing variables that are added to the environment of the command
+    fn env_file(&self) -> Option<String>;
+
     fn use_pty(&self) -> bool;
+
+    /// The maximum number of characters of the command that is written to the system log
+    fn syslog_maxlen(&self) -> Option<usize>;
+
+    /// Whether permitted invocations are written to the system log
+    fn log_allowed(&self) -> bool;
+
+    /// Whether denied invocations are written to the system log
+    fn log_denied(&self) -> bool;
+
+    /// Whether a mail is sent to the administrator for every invocation
+    fn mail_always(&self) -> bool;
+
+    /// The number of seconds after which the command is terminated
+    fn command_timeout(&self) -> Option<u32>;
+
+    /// Whether sudoedit may follow symbolic links when opening files
+    // FIXME: this is not used yet as sudoedit is not supported
+    #[allow(dead_code)]
+    fn sudoedit_follow(&self) -> bool;
 }
 
 #[must_use]
@@ -37,8 +61,18 @@ pub enum Authorization {
 #[cfg_attr(test, derive(Debug, PartialEq))]
 pub struct AuthorizationAllowed {
     pub must_authenticate: bool,
+    pub credential: AuthenticatingUser,
     pub allowed_attempts: u16,
     pub prior_validity: Duration,
+    pub bell: bool,
+}
+
+/// The user whose password needs to be entered when authenticating
+#[derive(Clone, Copy)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum AuthenticatingUser {
+    InvokingUser,
+    Root,
 }
 
 #[must_use]
@@ -55,8 +89,10 @@ impl Policy for Judgement {
             let valid_seconds = self.settings.int_value["timestamp_timeout"];
             Authorization::Allowed(AuthorizationAllowed {
                 must_authenticate: tag.needs_passwd(),
+                credential: credential(&self.settings),
                 allowed_attempts,
                 prior_validity: Duration::seconds(valid_seconds),
+                bell: self.settings.flags.contains("bell"),
             })
         } else {
             Authorization::Forbidden
@@ -85,13 +121,69 @@ impl Policy for Judgement {
             .map(|s| s.to_string())
     }
 
+    fn env_file(&self) -> Option<String> {
+        self.settings.str_value["env_file"]
+            .as_ref()
+            .map(|s| s.to_string())
+    }
+
     fn use_pty(&self) -> bool {
         self.settings.flags.contains("use_pty")
     }
+
+    fn syslog_maxlen(&self) -> Option<usize> {
+        match self.settings.int_value["syslog_maxlen"] {
+            0 => None,
+            maxlen => maxlen.try_into().ok(),
+        }
+    }
+
+    fn log_allowed(&self) -> bool {
+        self.settings.flags.contains("log_allowed")
+    }
+
+    fn log_denied(&self) -> bool {
+        self.settings.flags.contains("log_denied")
+    }
+
+    fn mail_always(&self) -> bool {
+        self.settings.flags.contains("mail_always")
+    }
+
+    fn command_timeout(&self) -> Option<u32> {
+        // a TIMEOUT tag on the command takes precedence over Defaults command_timeout
+        let seconds = match self.flags.as_ref().and_then(|tag| tag.timeout) {
+            Some(seconds) => seconds,
+            None => self.settings.int_value["command_timeout"].try_into().ok()?,
+        };
+
+        (seconds != 0).then_some(seconds)
+    }
+
+    fn sudoedit_follow(&self) -> bool {
+        // a FOLLOW or NOFOLLOW tag on the command takes precedence over Defaults sudoedit_follow
+        self.flags
+            .as_ref()
+            .and_then(|tag| tag.follow)
+            .unwrap_or_else(|| self.settings.flags.contains("sudoedit_follow"))
+    }
+}
+
+fn credential(settings: &Settings) -> AuthenticatingUser {
+    if settings.flags.contains("rootpw") {
+        AuthenticatingUser::Root
+    } else {
+        AuthenticatingUser::InvokingUser
+    }
 }
 
 pub trait PreJudgementPolicy {
     fn secure_path(&self) -> Option<String>;
+    fn runas_default(&self) -> &str;
+    fn fqdn(&self) -> bool;
+    /// Whether the invoking user may use `--close-from` to keep file descriptors open
+    fn closefrom_override(&self) -> bool;
+    fn syslog_pid(&self) -> bool;
     fn validate_authorization(&self) -> Authorization;
 }
 
@@ -102,11 +194,29 @@ impl PreJudgementPolicy for Sudoers {
             .map(|s| s.to_string())
     }
 
+    fn runas_default(&self) -> &str {
+        Sudoers::runas_default(self)
+    }
+
+    fn fqdn(&self) -> bool {
+        self.settings.flags.contains("fqdn")
+    }
+
+    fn closefrom_override(&self) -> bool {
+        self.settings.flags.contains("closefrom_override")
+    }
+
+    fn syslog_pid(&self) -> bool {
+        self.settings.flags.contains("syslog_pid")
+    }
+
     fn validate_authorization(&self) -> Authorization {
         Authorization::Allowed(AuthorizationAllowed {
             must_authenticate: true,
+            credential: credential(&self.settings),
             allowed_attempts: self.settings.int_value["passwd_tries"].try_into().unwrap(),
             prior_validity: Duration::seconds(self.settings.int_value["timestamp_timeout"]),
+            bell: self.settings.flags.contains("bell"),
         })
     }
 }
@@ -137,8 +247,10 @@ mod test {
             judge.authorization(),
             Authorization::Allowed(AuthorizationAllowed {
                 must_authenticate: true,
+                credential: AuthenticatingUser::InvokingUser,
                 allowed_attempts: 3,
                 prior_validity: Duration::minutes(15),
+                bell: false,
             })
         );
         judge.mod_flag(|tag| tag.authenticate = Authenticate::Nopasswd);
@@ -146,12 +258,32 @@ mod test {
             judge.authorization(),
             Authorization::Allowed(AuthorizationAllowed {
                 must_authenticate: false,
+                credential: AuthenticatingUser::InvokingUser,
                 allowed_attempts: 3,
                 prior_validity: Duration::minutes(15),
+                bell: false,
             })
         );
     }
 
+    #[test]
+    fn rootpw_test() {
+        let mut judge = Judgement {
+            flags: Some(Tag::default()),
+            ..Default::default()
+        };
+        let Authorization::Allowed(auth) = judge.authorization() else {
+            panic!();
+        };
+        assert_eq!(auth.credential, AuthenticatingUser::InvokingUser);
+
+        judge.settings.flags.insert("rootpw".to_string());
+        let Authorization::Allowed(auth) = judge.authorization() else {
+            panic!();
+        };
+        assert_eq!(auth.credential, AuthenticatingUser::Root);
+    }
+
     #[test]
     fn chdir_test() {
         let mut judge = Judgement {
@@ -166,4 +298,22 @@ mod test {
         judge.mod_flag(|tag| tag.cwd = Some(ChDir::Path("/bin".into())));
         assert_eq!(judge.chdir(), (DirChange::Strict(Some(Path::new("/bin")))));
     }
+
+    #[test]
+    fn command_timeout_test() {
+        let mut judge = Judgement {
+            flags: Some(Tag::default()),
+            ..Default::default()
+        };
+        assert_eq!(judge.command_timeout(), None);
+        judge
+            .settings
+            .int_value
+            .insert("command_timeout".to_string(), 60);
+        assert_eq!(judge.command_timeout(), Some(60));
+        judge.mod_flag(|tag| tag.timeout = Some(5));
+        assert_eq!(judge.command_timeout(), Some(5));
+        judge.mod_flag(|tag| tag.timeout = Some(0));
+        assert_eq!(judge.command_timeout(), None);
+    }
 }
@@ -85,7 +85,7 @@ fn permission_test() {
             let (Sudoers { rules,aliases,settings }, _) = analyze(Path::new("/etc/fakesudoers"), sudoer![$($sudo),*]);
             let cmdvec = $command.split_whitespace().map(String::from).collect::<Vec<_>>();
             let req = Request { user: $req.0, group: $req.1, command: &realpath(cmdvec[0].as_ref()), arguments: &cmdvec[1..].to_vec() };
-            assert_eq!(Sudoers { rules, aliases, settings }.check(&Named($user), $server, req).flags, None);
+            assert_eq!(Sudoers { rules, aliases, settings }.check(&Named($user), $server, &[], req).flags, None);
         }
     }
 
@@ -94,7 +94,7 @@ fn permission_test() {
             let (Sudoers { rules,aliases,settings }, _) = analyze(Path::new("/etc/fakesudoers"), sudoer![$($sudo),*]);
             let cmdvec = $command.split_whitespace().map(String::from).collect::<Vec<_>>();
             let req = Request { user: $req.0, group: $req.1, command: &realpath(cmdvec[0].as_ref()), arguments: &cmdvec[1..].to_vec() };
-            let result = Sudoers { rules, aliases, settings }.check(&Named($user), $server, req).flags;
+            let result = Sudoers { rules, aliases, settings }.check(&Named($user), $server, &[], req).flags;
             assert!(!result.is_none());
             $(
                 let result = result.unwrap();
@@ -125,6 +125,12 @@ fn permission_test() {
     pass!(["user ALL=(ALL:ALL) CWD=/usr/bin NOPASSWD: /bin/foo"], "user" => root(), "server"; "/bin/foo" => [authenticate: Authenticate::Nopasswd, cwd: Some(ChDir::Path("/usr/bin".into()))]);
     //note: original sudo does not allow the below
     pass!(["user ALL=(ALL:ALL) NOPASSWD: CWD=/usr/bin /bin/foo"], "user" => root(), "server"; "/bin/foo" => [authenticate: Authenticate::Nopasswd, cwd: Some(ChDir::Path("/usr/bin".into()))]);
+    pass!(["user ALL=(ALL:ALL) TIMEOUT=30 /bin/foo, /bin/bar"], "user" => root(), "server"; "/bin/bar" => [timeout: Some(30)]);
+    pass!(["user ALL=(ALL:ALL) TIMEOUT=30 /bin/foo, TIMEOUT=0 /bin/bar"], "user" => root(), "server"; "/bin/bar" => [timeout: Some(0)]);
+    SYNTAX!(["user ALL=(ALL:ALL) TIMEOUT=soon /bin/foo"]);
+    pass!(["user ALL=(ALL:ALL) FOLLOW: /bin/foo, /bin/bar"], "user" => root(), "server"; "/bin/bar" => [follow: Some(true)]);
+    pass!(["user ALL=(ALL:ALL) FOLLOW: NOFOLLOW: /bin/foo"], "user" => root(), "server"; "/bin/foo" => [follow: Some(false)]);
+    pass!(["user ALL=(ALL:ALL) /bin/foo"], "user" => root(), "server"; "/bin/foo" => [follow: None]);
 
     pass!(["user ALL=/bin/e##o"], "user" => root(), "vm"; "/bin/e");
     SYNTAX!(["ALL ALL=(ALL) /bin/\n/echo"]);
@@ -176,12 +182,21 @@ fn permission_test() {
     pass!(["foo,#1234,#1466 server=(ALL:ALL) ALL"], "user" => root(), "server"; "/bin/hello");
     FAIL!(["foo,#1234,#1366 server=(ALL:ALL) ALL"], "user" => root(), "server"; "/bin/hello");
     FAIL!(["#1366,#1234,foo server=(ALL:ALL) ALL"], "user" => root(), "server"; "/bin/hello");
+    FAIL!(["#4294967294 server=(ALL:ALL) ALL"], "user" => root(), "server"; "/bin/hello");
+    FAIL!(["%#4294967294 server=(ALL:ALL) ALL"], "user" => root(), "server"; "/bin/hello");
+    SYNTAX!(["#4294967296 server=(ALL:ALL) ALL"]);
+    SYNTAX!(["#12345678901 server=(ALL:ALL) ALL"]);
     pass!(["user ALL=(ALL:#1466) /bin/foo"], "user" => request! { root, root }, "server"; "/bin/foo");
     FAIL!(["user ALL=(ALL:#1466) /bin/foo"], "user" => request! { root, other }, "server"; "/bin/foo");
     pass!(["user ALL=(ALL:#1466) /bin/foo"], "user" => request! { root, user }, "server"; "/bin/foo");
     pass!(["user ALL=(root,user:ALL) /bin/foo"], "user" => request! { root, wheel }, "server"; "/bin/foo");
     pass!(["user ALL=(root,user:ALL) /bin/foo"], "user" => request! { user, wheel }, "server"; "/bin/foo");
     FAIL!(["user ALL=(root,user:ALL) /bin/foo"], "user" => request! { sudo, wheel }, "server"; "/bin/foo");
+    pass!(["user ALL=(ALL:ALL) /bin/foo"], "user" => request! { user, wheel }, "server"; "/bin/foo");
+    pass!(["user ALL=(user:ALL) /bin/foo"], "user" => request! { user, wheel }, "server"; "/bin/foo");
+    pass!(["user ALL=(:ALL) /bin/foo"], "user" => request! { user, wheel }, "server"; "/bin/foo");
+    FAIL!(["user ALL=(:ALL) /bin/foo"], "user" => request! { root, wheel }, "server"; "/bin/foo");
+    FAIL!(["user ALL=(user:ALL) /bin/foo"], "user" => request! { root, wheel }, "server"; "/bin/foo");
     FAIL!(["user ALL=(#0:wheel) /bin/foo"], "user" => request! { sudo, wheel }, "server"; "/bin/foo");
     pass!(["user ALL=(#0:wheel) /bin/foo"], "user" => request! { root, root }, "server"; "/bin/foo");
     FAIL!(["user ALL=(%#1466:wheel) /bin/foo"], "user" => request! { root, root }, "server"; "/bin/foo");
@@ -196,6 +211,8 @@ fn permission_test() {
     FAIL!(["user ALL=/bin/foo"], "user" => request! { sudo, root }, "server"; "/bin/foo");
     FAIL!(["user ALL=/bin/foo"], "user" => request! { root, sudo }, "server"; "/bin/foo");
     pass!(["user ALL=/bin/foo"], "user" => request! { root, root }, "server"; "/bin/foo");
+    pass!(["Defaults runas_default=sudo", "user ALL=/bin/foo"], "user" => request! { sudo, sudo }, "server"; "/bin/foo");
+    FAIL!(["Defaults runas_default=sudo", "user ALL=/bin/foo"], "user" => request! { root, root }, "server"; "/bin/foo");
 
     // slightly counterintuitive test which simulates only -g being passed
     pass!(["user ALL=(sudo:sudo) /bin/foo"], "user" => request! { user, sudo }, "server"; "/bin/foo");
@@ -228,12 +245,121 @@ fn permission_test() {
     FAIL!(["Runas_Alias TIME=%wheel,!!sudo","user ALL=(:TIME) ALL"], "user" => request! { sudo, sudo }, "vm"; "/bin/ls");
     pass!(["Runas_Alias TIME=%wheel,!!sudo","user ALL=(:TIME) ALL"], "user" => request! { user, sudo }, "vm"; "/bin/ls");
     pass!(["Runas_Alias TIME=%wheel,!!sudo","user ALL=(TIME) ALL"], "user" => request! { wheel, wheel }, "vm"; "/bin/ls");
+    pass!(["Runas_Alias OPS=%wheel,operator","user ALL=(OPS) ALL"], "user" => request! { wheel, wheel }, "vm"; "/bin/ls");
+    pass!(["Runas_Alias OPS=%wheel,operator","user ALL=(OPS) ALL"], "user" => request! { operator, operator }, "vm"; "/bin/ls");
+    FAIL!(["Runas_Alias OPS=%wheel,operator","user ALL=(OPS) ALL"], "user" => request! { sudo, sudo }, "vm"; "/bin/ls");
+    SYNTAX!(["Runas_Alias OPS=:wheel,operator"]);
 
     pass!(["Runas_Alias \\"," TIME=%wheel\\",",sudo # hallo","user ALL\\","=(TIME) ALL"], "user" => request! { wheel, wheel }, "vm"; "/bin/ls");
 
     // test the less-intuitive "substition-like" alias mechanism
     FAIL!(["User_Alias FOO=!user", "ALL, FOO ALL=ALL"], "user" => root(), "vm"; "/bin/ls");
     pass!(["User_Alias FOO=!user", "!FOO ALL=ALL"], "user" => root(), "vm"; "/bin/ls");
+
+    // host names without a domain match the local part of the hostname, unless fqdn is set
+    pass!(["user server=ALL"], "user" => root(), "server.example.com"; "/bin/ls");
+    pass!(["user server.example.com=ALL"], "user" => root(), "server.example.com"; "/bin/ls");
+    FAIL!(["user server.example.org=ALL"], "user" => root(), "server.example.com"; "/bin/ls");
+    FAIL!(["user example=ALL"], "user" => root(), "server.example.com"; "/bin/ls");
+    pass!(["Host_Alias SERVERS=server", "user SERVERS=ALL"], "user" => root(), "server.example.com"; "/bin/ls");
+    FAIL!(["Defaults fqdn", "user server=ALL"], "user" => root(), "server.example.com"; "/bin/ls");
+    pass!(["Defaults fqdn", "user server.example.com=ALL"], "user" => root(), "server.example.com"; "/bin/ls");
+}
+
+#[test]
+fn ip_host_matching() {
+    let host = |text: &str| Hostname(text.to_string());
+    let local_ips: Vec<std::net::IpAddr> = ["192.168.1.42", "10.0.0.1", "fe80::1", "2001:db8::7"]
+        .iter()
+        .map(|addr| addr.parse().unwrap())
+        .collect();
+    let matches = match_host("server", false, &local_ips);
+
+    // IPv4
+    assert!(matches(&host("192.168.1.42")));
+    assert!(!matches(&host("192.168.1.43")));
+    assert!(matches(&host("192.168.1.0/24")));
+    assert!(matches(&host("192.168.0.0/16")));
+    assert!(!matches(&host("192.168.2.0/24")));
+    assert!(matches(&host("192.168.1.0/255.255.255.0")));
+    assert!(!matches(&host("192.168.2.0/255.255.255.0")));
+    assert!(matches(&host("0.0.0.0/0")));
+
+    // IPv6
+    assert!(matches(&host("fe80::1")));
+    assert!(!matches(&host("fe80::2")));
+    assert!(matches(&host("fe80::/10")));
+    assert!(matches(&host("2001:db8::/32")));
+    assert!(!matches(&host("2001:db9::/32")));
+
+    // hostnames are not affected
+    assert!(matches(&host("server")));
+    assert!(!matches(&host("10.0.0.2")));
+
+    // no local addresses: nothing matches
+    let matches = match_host("server", false, &[]);
+    assert!(!matches(&host("0.0.0.0/0")));
+}
+
+#[test]
+fn ip_host_syntax() {
+    let Sudo::Spec(_) = parse_line("user 192.168.1.0/24=ALL") else {
+        panic!()
+    };
+    let Sudo::Spec(_) = parse_line("user 192.168.1.0/255.255.255.0, fe80::/10 = ALL") else {
+        panic!()
+    };
+    let Sudo::Decl(_) = parse_line("Host_Alias LAN = 10.0.0.0/8, ::1") else {
+        panic!()
+    };
+    assert!(parse_string::<Sudo>("user 192.168.1.0/33=ALL").is_err());
+    assert!(parse_string::<Sudo>("user 192.168.1.0/255.0.255.0=ALL").is_err());
+    assert!(parse_string::<Sudo>("user fe80::/129=ALL").is_err());
+    assert!(parse_string::<Sudo>("user server/24=ALL").is_err());
+    assert!(parse_string::<Sudo>("user fe80::/255.255.0.0=ALL").is_err());
+}
+
+#[test]
+fn host_alias_definitions_separated_by_colon() {
+    let host_aliases = |text: &str| {
+        let Sudo::Decl(Directive::HostAlias(defs)) = parse_line(text) else {
+            panic!("{text}")
+        };
+        defs.into_iter()
+            .map(|Def(name, hosts)| {
+                let hosts = hosts
+                    .into_iter()
+                    .map(|host| match host {
+                        Qualified::Allow(Meta::Only(Hostname(host))) => host,
+                        _ => panic!("{text}"),
+                    })
+                    .collect::<Vec<_>>();
+                (name, hosts)
+            })
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        host_aliases("Host_Alias A=h1:B=h2"),
+        [
+            ("A".to_string(), vec!["h1".to_string()]),
+            ("B".to_string(), vec!["h2".to_string()])
+        ]
+    );
+    assert_eq!(
+        host_aliases("Host_Alias A=cafe:B=h2"),
+        [
+            ("A".to_string(), vec!["cafe".to_string()]),
+            ("B".to_string(), vec!["h2".to_string()])
+        ]
+    );
+    assert_eq!(
+        host_aliases("Host_Alias A=fe80::/10:B=::1, h2"),
+        [
+            ("A".to_string(), vec!["fe80::/10".to_string()]),
+            ("B".to_string(), vec!["::1".to_string(), "h2".to_string()])
+        ]
+    );
 }
 
 #[test]
@@ -255,6 +381,142 @@ fn default_bool_test() {
     assert!(!settings.flags.contains("env_editor"));
 }
 
+#[test]
+fn editor_list_test() {
+    let (sudoers, _) = analyze(
+        Path::new("/etc/fakesudoers"),
+        sudoer![
+            "Defaults !env_editor",
+            "Defaults editor=/does/not/exist:/bin/sh:/bin/true"
+        ],
+    );
+    assert_eq!(sudoers.solve_editor_path().unwrap(), Path::new("/bin/sh"));
+
+    let (sudoers, _) = analyze(
+        Path::new("/etc/fakesudoers"),
+        sudoer!["Defaults !env_editor", "Defaults editor=/does/not/exist"],
+    );
+    assert_eq!(
+        sudoers.solve_editor_path().unwrap_err().to_string(),
+        "no editor found (editor path = /does/not/exist)"
+    );
+
+    assert!(parse_string::<Sudo>("Defaults editor=vi").is_err());
+    assert!(parse_string::<Sudo>("Defaults editor=/usr/bin/vi:vi").is_err());
+}
+
+#[test]
+fn sudoedit_follow_test() {
+    let follows = |defaults: &str, tag: &str| {
+        let (sudoers, _) = analyze(
+            Path::new("/etc/fakesudoers"),
+            sudoer![defaults, &format!("user ALL={tag} /bin/foo")],
+        );
+        let req = Request {
+            user: &Named("root"),
+            group: &Named("root"),
+            command: Path::new("/bin/foo"),
+            arguments: &[],
+        };
+        sudoers
+            .check(&Named("user"), "server", &[], req)
+            .sudoedit_follow()
+    };
+
+    assert!(!follows("", ""));
+    assert!(follows("", "FOLLOW:"));
+    assert!(!follows("", "NOFOLLOW:"));
+
+    // the tag on the command takes precedence over Defaults sudoedit_follow
+    assert!(follows("Defaults sudoedit_follow", ""));
+    assert!(follows("Defaults sudoedit_follow", "FOLLOW:"));
+    assert!(!follows("Defaults sudoedit_follow", "NOFOLLOW:"));
+    assert!(follows("Defaults !sudoedit_follow", "FOLLOW:"));
+}
+
+#[test]
+fn relative_command_test() {
+    let analyze_lines = |lines: &[&str]| {
+        let (sudoers, errors) = analyze(
+            Path::new("/etc/fakesudoers"),
+            parse_lines(&mut lines.join("\n").chars().peekable()),
+        );
+        let errors = errors
+            .into_iter()
+            .map(|Error(_, msg)| msg)
+            .collect::<Vec<_>>();
+        (sudoers, errors)
+    };
+    let allows = |sudoers: &Sudoers, cmd: &Path| {
+        let req = Request {
+            user: &Named("root"),
+            group: &Named("root"),
+            command: cmd,
+            arguments: &[],
+        };
+        sudoers
+            .check(&Named("user"), "server", &[], req)
+            .flags
+            .is_some()
+    };
+    let sh = canonicalize("/bin/sh").unwrap();
+
+    let (sudoers, errors) = analyze_lines(&["user ALL = ls", ""]);
+    assert_eq!(
+        errors,
+        ["ignoring rule: command 'ls' is not specified using an absolute path"]
+    );
+    assert!(sudoers.rules.is_empty());
+
+    // a relative negation must not leave the rest of the rule in place
+    let (sudoers, errors) = analyze_lines(&["user ALL = ALL, !passwd", ""]);
+    assert_eq!(errors.len(), 1);
+    assert!(!allows(&sudoers, &sh));
+
+    // the same holds for rules that refer to an alias containing a relative command
+    let (sudoers, errors) = analyze_lines(&[
+        "Cmnd_Alias PASSWD = passwd",
+        "Cmnd_Alias SHADOW = PASSWD, /usr/bin/chage",
+        "user ALL = ALL, !SHADOW",
+        "",
+    ]);
+    assert_eq!(
+        errors,
+        [
+            "ignoring Cmnd_Alias 'PASSWD': command 'passwd' is not specified using an absolute path",
+            "ignoring Cmnd_Alias 'SHADOW': Cmnd_Alias 'PASSWD' was ignored",
+            "ignoring rule: Cmnd_Alias 'SHADOW' was ignored",
+        ]
+    );
+    assert!(!allows(&sudoers, &sh));
+
+    // with a secure_path, relative commands are looked up in it when matching, not when parsing
+    let (sudoers, errors) =
+        analyze_lines(&["user ALL = sh", "Defaults secure_path=/does/not/exist", ""]);
+    assert!(errors.is_empty());
+    assert!(!allows(&sudoers, &sh));
+
+    let (sudoers, errors) = analyze_lines(&[
+        "user ALL = sh",
+        "Defaults secure_path=/does/not/exist:/bin",
+        "",
+    ]);
+    assert!(errors.is_empty());
+    assert!(allows(&sudoers, &sh));
+
+    let (sudoers, errors) =
+        analyze_lines(&["Defaults secure_path=/bin", "user ALL = ALL, !sh", ""]);
+    assert!(errors.is_empty());
+    assert!(!allows(&sudoers, &sh));
+    assert!(allows(&sudoers, Path::new("/usr/bin/true")));
+
+    let (_, errors) = analyze_lines(&["user ALL = /bin/sh, !/bin/ls", ""]);
+    assert!(errors.is_empty());
+
+    assert!(parse_string::<Sudo>("user ALL = sudoedit /etc/motd").is_err());
+    assert!(parse_string::<Sudo>("user ALL = sha224:0123 /bin/ls").is_err());
+}
+
 #[test]
 fn default_set_test() {
     let (Sudoers { settings, .. }, _) = analyze(
@@ -266,7 +528,8 @@ fn default_set_test() {
             "Defaults env_check += \"FOO\"",
             "Defaults env_check += \"XYZZY\"",
             "Defaults passwd_tries = 5",
-            "Defaults secure_path = /etc"
+            "Defaults secure_path = /etc",
+            "Defaults syslog_maxlen = 100"
         ],
     );
     assert_eq!(
@@ -282,12 +545,42 @@ fn default_set_test() {
     );
     assert_eq!(settings.str_value["secure_path"].as_deref(), Some("/etc"));
     assert_eq!(settings.int_value["passwd_tries"], 5);
+    assert_eq!(settings.int_value["syslog_maxlen"], 100);
 
     assert!(parse_string::<Sudo>("Defaults verifypw = \"sometimes\"").is_err());
     assert!(parse_string::<Sudo>("Defaults verifypw = sometimes").is_err());
     assert!(parse_string::<Sudo>("Defaults verifypw = never").is_ok());
 }
 
+#[test]
+fn default_unknown_test() {
+    let lines = [
+        "Defaults foo_bar",
+        "Defaults !baz, !env_reset",
+        "Defaults qux = \"a b\"",
+        "Defaults quux += x",
+    ];
+    let (Sudoers { settings, .. }, errors) = analyze(
+        Path::new("/etc/fakesudoers"),
+        sudoer![lines[0], lines[1], lines[2], lines[3]],
+    );
+    assert_eq!(errors.len(), 4);
+    assert_eq!(errors[0].1, "unknown setting: 'foo_bar'");
+    assert!(!settings.flags.contains("env_reset"));
+
+    let (_, errors) = analyze(
+        Path::new("/etc/fakesudoers"),
+        sudoer![
+            lines[0],
+            lines[1],
+            lines[2],
+            lines[3],
+            "Defaults ignore_unknown_defaults"
+        ],
+    );
+    assert!(errors.is_empty());
+}
+
 #[test]
 fn default_multi_test() {
     let (Sudoers { settings, .. }, _) = analyze(
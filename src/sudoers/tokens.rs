@@ -1,6 +1,8 @@
 //! Various tokens
 
-use super::basic_parser::{Many, Token};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use super::basic_parser::{CharStream, Many, Token};
 
 #[cfg_attr(test, derive(Clone, PartialEq, Eq))]
 pub struct Username(pub String);
@@ -25,10 +27,12 @@ impl Many for Username {}
 pub struct Digits(pub u32);
 
 impl Token for Digits {
-    const MAX_LEN: usize = 9;
+    const MAX_LEN: usize = 10;
 
     fn construct(s: String) -> Result<Self, String> {
-        Ok(Digits(s.parse().unwrap()))
+        s.parse()
+            .map(Digits)
+            .map_err(|_| "numeric id is out of range".to_string())
     }
 
     fn accept(c: char) -> bool {
@@ -50,9 +54,41 @@ impl Token for Numeric {
     }
 }
 
-/// A hostname consists of alphanumeric characters and ".", "-",  "_"
+/// A hostname consists of alphanumeric characters and ".", "-",  "_"; a host can also be specified
+/// as an IP address or network, e.g. "192.168.1.0/24", "192.168.1.0/255.255.255.0" or "fe80::/10"
 pub struct Hostname(pub String);
 
+impl Hostname {
+    /// The address and prefix length, if this host is specified as an IP address or network
+    pub fn ip_network(&self) -> Option<(IpAddr, u32)> {
+        let (addr, mask) = match self.split_once('/') {
+            Some((addr, mask)) => (addr, Some(mask)),
+            None => (self.as_str(), None),
+        };
+
+        let addr: IpAddr = addr.parse().ok()?;
+        let max_prefix = if addr.is_ipv4() { 32 } else { 128 };
+
+        let prefix = match mask {
+            None => max_prefix,
+            Some(mask) => match (mask.parse::<u32>(), mask.parse::<Ipv4Addr>()) {
+                (Ok(prefix), _) => prefix,
+                (_, Ok(netmask)) if addr.is_ipv4() => {
+                    let bits = u32::from(netmask);
+                    // only contiguous netmasks can be expressed as a prefix
+                    if bits.leading_ones() + bits.trailing_zeros() != 32 {
+                        return None;
+                    }
+                    bits.leading_ones()
+                }
+                _ => return None,
+            },
+        };
+
+        (prefix <= max_prefix).then_some((addr, prefix))
+    }
+}
+
 impl std::ops::Deref for Hostname {
     type Target = String;
 
@@ -63,11 +99,34 @@ impl std::ops::Deref for Hostname {
 
 impl Token for Hostname {
     fn construct(text: String) -> Result<Self, String> {
-        Ok(Hostname(text))
+        let host = Hostname(text);
+        if host.contains([':', '/']) && host.ip_network().is_none() {
+            Err(format!("invalid IP address or network: {}", host.0))
+        } else {
+            Ok(host)
+        }
     }
 
     fn accept(c: char) -> bool {
-        c.is_ascii_alphanumeric() || ".-_".contains(c)
+        c.is_ascii_alphanumeric() || ".-_/".contains(c)
+    }
+
+    fn accept_prefix(stream: &impl CharStream) -> usize {
+        // the length of "ffff:ffff:ffff:ffff:ffff:ffff:255.255.255.255"
+        const MAX_IPV6_LEN: usize = 45;
+
+        // a ':' also separates alias definitions, so it is only part of a host if it is (the
+        // first part of) an IPv6 address; the longest such address is taken
+        let candidate = (0..MAX_IPV6_LEN)
+            .map_while(|n| stream.peek_nth(n))
+            .take_while(|c| c.is_ascii_hexdigit() || ":.".contains(*c))
+            .collect::<String>();
+
+        (1..=candidate.len())
+            .rev()
+            .map(|len| &candidate[..len])
+            .find(|addr| addr.contains(':') && addr.parse::<Ipv6Addr>().is_ok())
+            .map_or(0, str::len)
     }
 }
 
@@ -108,6 +167,10 @@ impl<T: Token> Token for Meta<T> {
         T::accept_1st(c) || c.is_uppercase()
     }
 
+    fn accept_prefix(stream: &impl CharStream) -> usize {
+        T::accept_prefix(stream)
+    }
+
     const ESCAPE: char = T::ESCAPE;
 
     fn escaped(c: char) -> bool {
@@ -152,6 +215,16 @@ impl Token for Command {
         // the tokenizer should not give us a token that consists of only whitespace
         let mut cmd_iter = s.split_whitespace();
         let mut cmd = cmd_iter.next().unwrap().to_string();
+
+        if cmd == "sudoedit" {
+            // note: special behaviour of forward slashes in wildcards, tread carefully
+            return Err("sudoedit is not yet supported".to_string());
+        } else if cmd == "list" {
+            return Err("list is not yet supported".to_string());
+        } else if cmd.starts_with("sha") {
+            return Err("digest specifications are not supported".to_string());
+        }
+
         let mut args = cmd_iter.map(String::from).collect::<Vec<String>>();
 
         let argpat = if args.is_empty() {
@@ -171,12 +244,14 @@ impl Token for Command {
             true
         };
 
-        // canonicalize path (if possible)
-        if let Ok(real_cmd) = crate::common::resolve::canonicalize(&cmd) {
-            cmd = real_cmd
-                .to_str()
-                .ok_or("non-UTF8 characters in filesystem")?
-                .to_string();
+        // canonicalize path (if possible); relative paths are checked after parsing
+        if cmd.starts_with('/') {
+            if let Ok(real_cmd) = crate::common::resolve::canonicalize(&cmd) {
+                cmd = real_cmd
+                    .to_str()
+                    .ok_or("non-UTF8 characters in filesystem")?
+                    .to_string();
+            }
         }
 
         // if the cmd ends with a slash, any command in that directory is allowed
@@ -187,9 +262,10 @@ impl Token for Command {
         Ok((cvt_err(glob::Pattern::new(&cmd))?, argpat))
     }
 
-    // all commands start with "/" except "sudoedit"
+    // commands should start with "/", but relative names are accepted here so they can be
+    // reported (or resolved using secure_path) once the entire sudoers file has been read
     fn accept_1st(c: char) -> bool {
-        c == '/' || c == 's'
+        c == '/' || Username::accept_1st(c)
     }
 
     fn accept(c: char) -> bool {
@@ -1,10 +1,15 @@
 // TODO: remove unused attribute when system is cleaned up
 use std::{
     collections::BTreeSet,
-    ffi::{c_uint, CStr, CString},
+    ffi::{c_int, c_uint, CStr, CString},
+    fs::File,
     io,
     mem::MaybeUninit,
-    os::{fd::AsRawFd, unix::prelude::OsStrExt},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    os::{
+        fd::{AsRawFd, FromRawFd, RawFd},
+        unix::prelude::OsStrExt,
+    },
     path::{Path, PathBuf},
     str::FromStr,
 };
@@ -94,6 +99,31 @@ impl FileCloser {
     }
 }
 
+/// List the file descriptors below `max_fd` (other than the IO streams) that are open and will
+/// be inherited by a child process, i.e. that do not have the close-on-exec flag set. Only the
+/// file descriptors that are actually open are inspected, so this is cheap for any `max_fd`.
+pub(crate) fn inheritable_fds(max_fd: c_int) -> io::Result<Vec<RawFd>> {
+    let mut fds = Vec::new();
+    for entry in std::fs::read_dir("/proc/self/fd")? {
+        let Ok(fd) = entry?.file_name().to_string_lossy().parse::<RawFd>() else {
+            continue;
+        };
+        if fd <= STDERR_FILENO || fd >= max_fd {
+            continue;
+        }
+
+        // SAFETY: F_GETFD only queries the flags of `fd`, which may have been closed since (e.g.
+        // the file descriptor of the directory listing itself).
+        let flags = unsafe { libc::fcntl(fd, libc::F_GETFD) };
+        if flags != -1 && flags & libc::FD_CLOEXEC == 0 {
+            fds.push(fd);
+        }
+    }
+    fds.sort_unstable();
+
+    Ok(fds)
+}
+
 fn close_range(min_fd: c_uint, max_fd: c_uint) -> io::Result<()> {
     if min_fd <= max_fd {
         cerr(unsafe { libc::syscall(libc::SYS_close_range, min_fd, max_fd, 0 as c_uint) })?;
@@ -137,35 +167,255 @@ pub(crate) unsafe fn fork() -> io::Result<ForkResult> {
     inner_fork()
 }
 
+/// Detach from the controlling terminal and continue running in the background. The calling
+/// process exits and execution continues in a grandchild that is not a session leader (so it can
+/// never reacquire a controlling terminal), with its standard IO redirected to `/dev/null` and `/`
+/// as its working directory.
+// FIXME: this is not used yet as `--background` is not supported
+#[allow(dead_code)]
+pub(crate) fn daemonise() -> io::Result<()> {
+    // SAFETY: the parent processes exit right away, without doing anything that is not
+    // async-signal-safe.
+    if let ForkResult::Parent(_) = unsafe { inner_fork() }? {
+        _exit(0);
+    }
+
+    setsid()?;
+
+    // SAFETY: see above
+    if let ForkResult::Parent(_) = unsafe { inner_fork() }? {
+        _exit(0);
+    }
+
+    let dev_null = File::options().read(true).write(true).open("/dev/null")?;
+    for fd in [libc::STDIN_FILENO, libc::STDOUT_FILENO, libc::STDERR_FILENO] {
+        cerr(unsafe { libc::dup2(dev_null.as_raw_fd(), fd) })?;
+    }
+
+    std::env::set_current_dir("/")
+}
+
+/// Write the PID of a process to a newly created file at `path`; fails if the file already exists.
+// FIXME: this is not used yet as `--background` is not supported
+#[allow(dead_code)]
+pub(crate) fn write_pidfile(path: &Path, pid: ProcessId) -> io::Result<()> {
+    use std::{io::Write, os::unix::fs::OpenOptionsExt};
+
+    // `create_new` opens the file with O_CREAT | O_EXCL, so an existing file is never clobbered
+    let mut file = File::options()
+        .write(true)
+        .create_new(true)
+        .mode(0o644)
+        .open(path)?;
+
+    writeln!(file, "{pid}")
+}
+
+/// Read the PID of a process from a file written by [`write_pidfile`].
+// FIXME: this is not used yet as `--background` is not supported
+#[allow(dead_code)]
+pub(crate) fn read_pidfile(path: &Path) -> io::Result<ProcessId> {
+    std::fs::read_to_string(path)?
+        .trim()
+        .parse()
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "invalid pidfile contents"))
+}
+
+/// Make a path absolute and resolve all `.` and `..` components as well as symbolic links in the
+/// directories leading up to the last component, but do not follow a symbolic link in the last
+/// component itself. The last component does not need to exist.
+// FIXME: this is not used yet as sudoedit is not supported
+#[allow(dead_code)]
+pub(crate) fn canonicalize_path_no_follow(path: &Path) -> io::Result<PathBuf> {
+    const MAX_SYMLINKS: usize = 40;
+
+    let mut resolved = if path.is_absolute() {
+        PathBuf::from("/")
+    } else {
+        std::env::current_dir()?
+    };
+
+    // the components that still need to be resolved, in reverse order
+    let mut pending = path
+        .components()
+        .rev()
+        .map(|component| component.as_os_str().to_owned())
+        .collect::<Vec<_>>();
+    let mut symlinks_followed = 0;
+
+    while let Some(component) = pending.pop() {
+        if component == "/" {
+            resolved = PathBuf::from("/");
+        } else if component == "." {
+            // nothing to do
+        } else if component == ".." {
+            resolved.pop();
+        } else {
+            let candidate = resolved.join(&component);
+            if pending.is_empty() {
+                resolved = candidate;
+                continue;
+            }
+
+            let metadata = std::fs::symlink_metadata(&candidate)?;
+            if metadata.file_type().is_symlink() {
+                symlinks_followed += 1;
+                if symlinks_followed > MAX_SYMLINKS {
+                    return Err(io::Error::from_raw_os_error(libc::ELOOP));
+                }
+
+                let target = std::fs::read_link(&candidate)?;
+                pending.extend(
+                    target
+                        .components()
+                        .rev()
+                        .map(|component| component.as_os_str().to_owned()),
+                );
+            } else if metadata.is_dir() {
+                resolved = candidate;
+            } else {
+                return Err(io::Error::from_raw_os_error(libc::ENOTDIR));
+            }
+        }
+    }
+
+    Ok(resolved)
+}
+
+/// Check whether two paths refer to the same file by comparing their device and inode numbers.
+/// Symbolic links in either path are followed.
+pub(crate) fn is_same_file(path_a: &Path, path_b: &Path) -> io::Result<bool> {
+    use std::os::unix::fs::MetadataExt;
+
+    let (meta_a, meta_b) = (std::fs::metadata(path_a)?, std::fs::metadata(path_b)?);
+
+    Ok(meta_a.dev() == meta_b.dev() && meta_a.ino() == meta_b.ino())
+}
+
 pub fn setsid() -> io::Result<ProcessId> {
     cerr(unsafe { libc::setsid() })
 }
 
+/// Create a pipe, returning its read and write end respectively. Both ends are closed on `exec`.
+pub fn create_pipe() -> io::Result<(File, File)> {
+    let mut fds: [c_int; 2] = [0; 2];
+
+    #[cfg(any(target_os = "linux", target_os = "freebsd"))]
+    cerr(unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC) })?;
+    #[cfg(not(any(target_os = "linux", target_os = "freebsd")))]
+    cerr(unsafe { libc::pipe(fds.as_mut_ptr()) })?;
+
+    // SAFETY: both file descriptors were just created and are not owned by anything else
+    let (rx, tx) = unsafe { (File::from_raw_fd(fds[0]), File::from_raw_fd(fds[1])) };
+
+    // without `pipe2` there is a window in which the file descriptors could leak into a process
+    // that is forked and executed by another thread; sudo is single-threaded so that is fine
+    #[cfg(not(any(target_os = "linux", target_os = "freebsd")))]
+    for fd in [&rx, &tx] {
+        cerr(unsafe { libc::fcntl(fd.as_raw_fd(), libc::F_SETFD, libc::FD_CLOEXEC) })?;
+    }
+
+    Ok((rx, tx))
+}
+
 pub fn geteuid() -> io::Result<u32> {
     cerr(unsafe { libc::geteuid() })
 }
 
-pub fn hostname() -> String {
+/// Retrieve the name of the current host, as reported by `gethostname(2)`
+pub fn get_hostname() -> io::Result<String> {
     // see `man 2 gethostname`
     const MAX_HOST_NAME_SIZE_ACCORDING_TO_SUSV2: libc::c_long = 255;
 
+    // give up when the hostname is absurdly long rather than allocating without bound
+    const MAX_BUFFER_SIZE: usize = 64 * 1024;
+
     // POSIX.1 systems limit hostnames to `HOST_NAME_MAX` bytes
     // not including null-byte in the count
     let max_hostname_size =
         sysconf(libc::_SC_HOST_NAME_MAX).unwrap_or(MAX_HOST_NAME_SIZE_ACCORDING_TO_SUSV2) as usize;
 
-    let buffer_size = max_hostname_size + 1 /* null byte delimiter */ ;
-    let mut buf = vec![0; buffer_size];
+    let mut buffer_size = max_hostname_size + 1 /* null byte delimiter */ ;
+    while buffer_size <= MAX_BUFFER_SIZE {
+        let mut buf = vec![0; buffer_size];
+        match cerr(unsafe { libc::gethostname(buf.as_mut_ptr(), buffer_size) }) {
+            // POSIX leaves it unspecified whether a truncated hostname is null terminated
+            Ok(_) if buf.contains(&0) => return Ok(unsafe { string_from_ptr(buf.as_ptr()) }),
+            Ok(_) => {}
+            // ENAMETOOLONG is returned when hostname is greater than `buffer_size`
+            Err(err) if err.raw_os_error() == Some(libc::ENAMETOOLONG) => {}
+            Err(err) => return Err(err),
+        }
+        buffer_size *= 2;
+    }
+
+    Err(io::Error::from_raw_os_error(libc::ENAMETOOLONG))
+}
+
+/// Resolve the fully qualified domain name of `hostname` using `getaddrinfo(3)`; note that this
+/// can involve a DNS query
+pub fn get_fqdn(hostname: &str) -> io::Result<String> {
+    let c_hostname = CString::new(hostname)?;
+    // SAFETY: all-zeroes is a valid value for this plain C struct
+    let mut hints: libc::addrinfo = unsafe { MaybeUninit::zeroed().assume_init() };
+    hints.ai_family = libc::AF_UNSPEC;
+    hints.ai_flags = libc::AI_CANONNAME;
+
+    let mut addrinfo = std::ptr::null_mut();
+    match unsafe { libc::getaddrinfo(c_hostname.as_ptr(), std::ptr::null(), &hints, &mut addrinfo) }
+    {
+        0 => {}
+        libc::EAI_SYSTEM => return Err(io::Error::last_os_error()),
+        error => {
+            let message = unsafe { string_from_ptr(libc::gai_strerror(error)) };
+            return Err(io::Error::new(io::ErrorKind::Other, message));
+        }
+    }
 
-    match cerr(unsafe { libc::gethostname(buf.as_mut_ptr(), buffer_size) }) {
-        Ok(_) => unsafe { string_from_ptr(buf.as_ptr()) },
+    // only the first entry carries the canonical name
+    let fqdn = match unsafe { string_from_ptr((*addrinfo).ai_canonname) } {
+        name if name.is_empty() => hostname.to_string(),
+        name => name,
+    };
+    unsafe { libc::freeaddrinfo(addrinfo) };
+
+    Ok(fqdn)
+}
 
-        // ENAMETOOLONG is returned when hostname is greater than `buffer_size`
-        Err(_) => {
-            // but we have chosen a `buffer_size` larger than `max_hostname_size` so no truncation error is possible
-            panic!("Unexpected error while retrieving hostname, this should not happen");
+/// Retrieve the IP addresses assigned to the network interfaces of the current host; like the
+/// original sudo, interfaces that are down and loopback interfaces are skipped
+pub fn get_local_ips() -> io::Result<Vec<IpAddr>> {
+    let mut ifaddrs = std::ptr::null_mut();
+    cerr(unsafe { libc::getifaddrs(&mut ifaddrs) })?;
+
+    let mut addresses = Vec::new();
+    let mut current = ifaddrs;
+    while !current.is_null() {
+        // SAFETY: `current` is a non-null element of the list returned by `getifaddrs`
+        let ifaddr = unsafe { &*current };
+        let flags = ifaddr.ifa_flags as libc::c_int;
+        let usable = flags & libc::IFF_UP != 0 && flags & libc::IFF_LOOPBACK == 0;
+        if usable && !ifaddr.ifa_addr.is_null() {
+            // SAFETY: the `sa_family` field tells us which kind of `sockaddr` this really is
+            match libc::c_int::from(unsafe { (*ifaddr.ifa_addr).sa_family }) {
+                libc::AF_INET => {
+                    let addr = unsafe { &*(ifaddr.ifa_addr as *const libc::sockaddr_in) };
+                    let octets = addr.sin_addr.s_addr.to_ne_bytes();
+                    addresses.push(IpAddr::V4(Ipv4Addr::from(octets)));
+                }
+                libc::AF_INET6 => {
+                    let addr = unsafe { &*(ifaddr.ifa_addr as *const libc::sockaddr_in6) };
+                    addresses.push(IpAddr::V6(Ipv6Addr::from(addr.sin6_addr.s6_addr)));
+                }
+                _ => {}
+            }
         }
+        current = ifaddr.ifa_next;
     }
+
+    unsafe { libc::freeifaddrs(ifaddrs) };
+
+    Ok(addresses)
 }
 
 pub fn syslog(priority: libc::c_int, facility: libc::c_int, message: &str) {
@@ -181,6 +431,19 @@ pub fn syslog(priority: libc::c_int, facility: libc::c_int, message: &str) {
     }
 }
 
+/// Include the process ID in all subsequent messages that are sent to the system log.
+pub fn syslog_include_pid() {
+    const IDENT: *const libc::c_char = match CStr::from_bytes_until_nul(b"sudo\0") {
+        Ok(cstr) => cstr.as_ptr(),
+        Err(_) => panic!("syslog identifier is not null-terminated"),
+    };
+
+    // SAFETY: `IDENT` is a static nul-terminated string, so it stays valid as `openlog` requires.
+    unsafe {
+        libc::openlog(IDENT, libc::LOG_PID, libc::LOG_AUTH);
+    }
+}
+
 /// set target user and groups (uid, gid, additional groups) for a command
 pub fn set_target_user(
     cmd: &mut std::process::Command,
@@ -218,6 +481,39 @@ pub fn kill(pid: ProcessId, signal: SignalNumber) -> io::Result<()> {
     cerr(unsafe { libc::kill(pid, signal) }).map(|_| ())
 }
 
+/// Arrange for `SIGALRM` to be delivered to the calling process in `seconds` seconds.
+pub fn alarm(seconds: u32) {
+    // SAFETY: This function cannot cause UB, a pending alarm is simply replaced.
+    unsafe { libc::alarm(seconds) };
+}
+
+/// The maximum length of a process name, excluding the nul terminator.
+const PROCESS_NAME_MAX: usize = 15;
+
+/// Set the name of the calling process as shown by `ps` and in `/proc/self/status`. Names that are
+/// longer than 15 bytes are truncated.
+pub(crate) fn set_process_name(name: &str) -> io::Result<()> {
+    let mut len = name.len().min(PROCESS_NAME_MAX);
+    while !name.is_char_boundary(len) {
+        len -= 1;
+    }
+
+    let name = CString::new(&name[..len])
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "nul byte in process name"))?;
+
+    #[cfg(target_os = "linux")]
+    // SAFETY: `name` is a valid nul-terminated string, the kernel copies it.
+    cerr(unsafe { libc::prctl(libc::PR_SET_NAME, name.as_ptr() as libc::c_ulong, 0, 0, 0) })?;
+
+    #[cfg(not(target_os = "linux"))]
+    // SAFETY: `setprogname` keeps the pointer, so the string is leaked to keep it valid.
+    unsafe {
+        libc::setprogname(name.into_raw())
+    };
+
+    Ok(())
+}
+
 /// Send a signal to a process group with the specified ID.
 pub fn killpg(pgid: ProcessId, signal: SignalNumber) -> io::Result<()> {
     // SAFETY: This function cannot cause UB even if `pgid` is not a valid process ID or if
@@ -661,6 +957,126 @@ mod tests {
             .open(path)
     }
 
+    #[test]
+    fn canonicalize_does_not_follow_last_component() {
+        use super::canonicalize_path_no_follow as canonicalize;
+        use std::{os::unix::fs::symlink, path::Path};
+
+        let base = std::env::temp_dir().join(format!("sudo_rs_test_{}_canon", std::process::id()));
+        std::fs::create_dir_all(base.join("dir")).unwrap();
+        std::fs::write(base.join("dir/file"), "").unwrap();
+        symlink(base.join("dir"), base.join("dir_link")).unwrap();
+        symlink("dir/file", base.join("file_link")).unwrap();
+
+        let real_base = std::fs::canonicalize(&base).unwrap();
+        let results = [
+            canonicalize(&base.join("file_link")),
+            canonicalize(&base.join("dir/../file_link")),
+            canonicalize(&base.join("dir_link/file")),
+            canonicalize(&base.join("dir_link/../dir/./new_file")),
+            canonicalize(&base.join("dir_link")),
+            canonicalize(&base.join("dir/file/oops")),
+        ];
+        std::fs::remove_dir_all(&base).unwrap();
+
+        let results = results.map(|result| result.map_err(|err| err.raw_os_error()));
+        assert_eq!(results[0], Ok(real_base.join("file_link")));
+        assert_eq!(results[1], Ok(real_base.join("file_link")));
+        assert_eq!(results[2], Ok(real_base.join("dir/file")));
+        assert_eq!(results[3], Ok(real_base.join("dir/new_file")));
+        assert_eq!(results[4], Ok(real_base.join("dir_link")));
+        assert_eq!(results[5], Err(Some(libc::ENOTDIR)));
+
+        assert_eq!(canonicalize(Path::new("/..")).unwrap(), Path::new("/"));
+    }
+
+    #[test]
+    fn same_file_through_links() {
+        use super::is_same_file;
+        use std::os::unix::fs::symlink;
+
+        let base = std::env::temp_dir().join(format!("sudo_rs_test_{}_same", std::process::id()));
+        std::fs::create_dir_all(&base).unwrap();
+        std::fs::write(base.join("file"), "").unwrap();
+        std::fs::write(base.join("copy"), "").unwrap();
+        std::fs::hard_link(base.join("file"), base.join("hard_link")).unwrap();
+        symlink("file", base.join("symlink")).unwrap();
+
+        let results = [
+            is_same_file(&base.join("file"), &base.join("file")),
+            is_same_file(&base.join("file"), &base.join("hard_link")),
+            is_same_file(&base.join("symlink"), &base.join("file")),
+            is_same_file(&base.join("hard_link"), &base.join("symlink")),
+            is_same_file(&base.join("file"), &base.join("copy")),
+            is_same_file(&base.join("file"), &base.join("missing")),
+        ];
+        std::fs::remove_dir_all(&base).unwrap();
+
+        let results = results.map(|result| result.map_err(|err| err.kind()));
+        assert_eq!(results[0], Ok(true));
+        assert_eq!(results[1], Ok(true));
+        assert_eq!(results[2], Ok(true));
+        assert_eq!(results[3], Ok(true));
+        assert_eq!(results[4], Ok(false));
+        assert_eq!(results[5], Err(io::ErrorKind::NotFound));
+    }
+
+    #[test]
+    fn process_name_is_truncated() {
+        let ForkResult::Parent(child_pid) = fork().unwrap() else {
+            super::set_process_name("sudo: ls").unwrap();
+            assert_eq!(
+                std::fs::read_to_string("/proc/self/comm").unwrap(),
+                "sudo: ls\n"
+            );
+
+            super::set_process_name("sudo: a-very-long-command").unwrap();
+            assert_eq!(
+                std::fs::read_to_string("/proc/self/comm").unwrap(),
+                "sudo: a-very-lo\n"
+            );
+
+            // do not split multi-byte characters
+            super::set_process_name("sudo: command-é").unwrap();
+            assert_eq!(
+                std::fs::read_to_string("/proc/self/comm").unwrap(),
+                "sudo: command-\n"
+            );
+
+            assert!(super::set_process_name("sudo\0ls").is_err());
+
+            exit(0)
+        };
+
+        let (_, status) = child_pid.wait(WaitOptions::new()).unwrap();
+        assert_eq!(status.exit_status(), Some(0));
+    }
+
+    #[test]
+    fn pidfile_roundtrip() {
+        let path = std::env::temp_dir().join(format!("sudo_rs_test_{}.pid", std::process::id()));
+
+        super::write_pidfile(&path, 1234).unwrap();
+        let pid = super::read_pidfile(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(pid.unwrap(), 1234);
+    }
+
+    #[test]
+    fn pidfile_is_not_overwritten() {
+        let path =
+            std::env::temp_dir().join(format!("sudo_rs_test_{}_exists.pid", std::process::id()));
+
+        super::write_pidfile(&path, 1234).unwrap();
+        let err = super::write_pidfile(&path, 5678).unwrap_err();
+        let pid = super::read_pidfile(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(pid.unwrap(), 1234);
+    }
+
     #[test]
     fn test_get_user_and_group_by_id() {
         let fixed_users = &[(0, "root"), (1, "daemon")];
@@ -716,6 +1132,83 @@ mod tests {
         test("abc", "password123", 42, &[""]);
     }
 
+    #[test]
+    fn get_hostname_matches_kernel_hostname() {
+        let hostname = super::get_hostname().unwrap();
+        assert!(!hostname.is_empty());
+
+        // on Linux the hostname set through `sethostname(2)` is also exposed via procfs
+        #[cfg(target_os = "linux")]
+        assert_eq!(
+            hostname,
+            std::fs::read_to_string("/proc/sys/kernel/hostname")
+                .unwrap()
+                .trim_end()
+        );
+    }
+
+    #[test]
+    fn get_fqdn_does_not_resolve_addresses() {
+        // numeric addresses are returned as-is by `getaddrinfo`, without doing any lookups
+        assert_eq!(super::get_fqdn("127.0.0.1").unwrap(), "127.0.0.1");
+        assert!(super::get_fqdn("local\0host").is_err());
+    }
+
+    #[test]
+    fn get_local_ips_excludes_loopback() {
+        let addresses = super::get_local_ips().unwrap();
+        assert!(!addresses.iter().any(|addr| addr.is_loopback()));
+    }
+
+    #[test]
+    fn create_pipe_sets_cloexec() {
+        let (mut rx, mut tx) = super::create_pipe().unwrap();
+
+        for fd in [&rx, &tx] {
+            let flags = unsafe { libc::fcntl(fd.as_raw_fd(), libc::F_GETFD) };
+            assert_ne!(flags, -1);
+            assert_ne!(flags & libc::FD_CLOEXEC, 0);
+        }
+
+        tx.write_all(b"sudo").unwrap();
+        drop(tx);
+        let mut buf = String::new();
+        rx.read_to_string(&mut buf).unwrap();
+        assert_eq!(buf, "sudo");
+    }
+
+    #[test]
+    fn daemonise_starts_new_session() {
+        let (mut rx, mut tx) = super::create_pipe().unwrap();
+
+        let ForkResult::Parent(child_pid) = fork().unwrap() else {
+            drop(rx);
+            if super::daemonise().is_err() {
+                exit(1)
+            }
+            let session_id = super::Process::session_id();
+            let pid = std::process::id() as super::ProcessId;
+            tx.write_all(&session_id.to_ne_bytes()).unwrap();
+            tx.write_all(&pid.to_ne_bytes()).unwrap();
+            exit(0)
+        };
+        drop(tx);
+
+        // the direct child exits as part of daemonising
+        let (_, status) = child_pid.wait(WaitOptions::new()).unwrap();
+        assert_eq!(status.exit_status(), Some(0));
+
+        let mut buf = [0; 4];
+        rx.read_exact(&mut buf).unwrap();
+        let session_id = i32::from_ne_bytes(buf);
+        rx.read_exact(&mut buf).unwrap();
+        let daemon_pid = i32::from_ne_bytes(buf);
+
+        assert_ne!(session_id, super::Process::session_id());
+        // the daemon is not the session leader, so it cannot acquire a controlling terminal
+        assert_ne!(session_id, daemon_pid);
+    }
+
     #[test]
     fn get_process_tty_device() {
         assert!(super::Process::tty_device_id(WithProcess::Current).is_ok());
@@ -829,6 +1322,32 @@ mod tests {
         assert_eq!(status.exit_status(), Some(0));
     }
 
+    #[test]
+    fn inheritable_fds_skips_close_on_exec() {
+        let ForkResult::Parent(child_pid) = fork().unwrap() else {
+            // files opened by the standard library have the close-on-exec flag set
+            let cloexec = std::fs::File::open("/dev/null").unwrap();
+            let inheritable = unsafe { libc::dup(cloexec.as_raw_fd()) };
+            assert!(inheritable > cloexec.as_raw_fd());
+
+            let fds = super::inheritable_fds(inheritable + 1).unwrap();
+            assert!(fds.contains(&inheritable));
+            assert!(!fds.contains(&cloexec.as_raw_fd()));
+            assert!(!super::inheritable_fds(inheritable)
+                .unwrap()
+                .contains(&inheritable));
+            // the upper bound does not affect the cost of the lookup
+            assert!(super::inheritable_fds(libc::c_int::MAX)
+                .unwrap()
+                .contains(&inheritable));
+
+            exit(0)
+        };
+
+        let (_, status) = child_pid.wait(WaitOptions::new()).unwrap();
+        assert_eq!(status.exit_status(), Some(0));
+    }
+
     #[test]
     fn except_stdio_is_fine() {
         let ForkResult::Parent(child_pid) = fork().unwrap() else {
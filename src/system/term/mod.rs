@@ -5,7 +5,7 @@ use std::{
     fmt,
     fs::File,
     io,
-    os::fd::{AsRawFd, FromRawFd, OwnedFd},
+    os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd},
     ptr::null_mut,
 };
 
@@ -186,6 +186,15 @@ pub fn current_tty_name() -> io::Result<OsString> {
     std::io::stdin().ttyname()
 }
 
+/// Get the name of the terminal `fd` refers to in the form used in log entries, i.e. without the
+/// leading `/dev/` (`/dev/pts/0` becomes `pts/0`). Returns `None` if `fd` is not a terminal.
+pub(crate) fn get_tty_name(fd: RawFd) -> Option<String> {
+    let path = fd.ttyname().ok()?;
+    let path = path.to_string_lossy();
+
+    Some(path.strip_prefix("/dev/").unwrap_or(&path).to_string())
+}
+
 #[repr(transparent)]
 pub(crate) struct TermSize {
     raw: winsize,
@@ -209,11 +218,11 @@ mod tests {
         ffi::OsString,
         io::{Read, Write},
         os::unix::{net::UnixStream, prelude::OsStringExt},
-        path::PathBuf,
+        path::{Path, PathBuf},
         process::exit,
     };
 
-    use crate::system::{fork, getpgid, setsid, term::*, ForkResult};
+    use crate::system::{create_pipe, fork, getpgid, setsid, term::*, ForkResult};
 
     #[test]
     fn open_pty() {
@@ -226,6 +235,23 @@ mod tests {
         assert!(path.starts_with("/dev/pts/"));
     }
 
+    #[test]
+    fn tty_name_of_pipe_is_none() {
+        let (rx, tx) = create_pipe().unwrap();
+        assert_eq!(get_tty_name(rx.as_raw_fd()), None);
+        assert_eq!(get_tty_name(tx.as_raw_fd()), None);
+    }
+
+    #[test]
+    fn tty_name_of_pty_is_short_form() {
+        let pty = Pty::open().unwrap();
+        let name = get_tty_name(pty.follower.as_raw_fd()).unwrap();
+
+        let path = PathBuf::from(OsString::from_vec(pty.path.into_bytes()));
+        assert!(name.starts_with("pts/"));
+        assert_eq!(Path::new("/dev").join(&name), path);
+    }
+
     #[test]
     fn tcsetpgrp_and_tcgetpgrp_are_consistent() {
         // Create a socket so the child can send us a byte if successful.
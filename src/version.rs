@@ -0,0 +1,44 @@
+#![forbid(unsafe_code)]
+
+use std::path::Path;
+
+const BUILD_DATE: &str = env!("SUDO_BUILD_DATE");
+const BUILD_TARGET: &str = env!("SUDO_BUILD_TARGET");
+
+/// The features this binary was built with
+fn features() -> Vec<&'static str> {
+    [("PAM", cfg!(pam)), ("dev", cfg!(feature = "dev"))]
+        .into_iter()
+        .filter_map(|(name, enabled)| enabled.then_some(name))
+        .collect()
+}
+
+/// Details about the configuration of this build, as shown by `sudo --version`
+pub(crate) fn build_info(sudoers_path: &Path) -> String {
+    format!(
+        "Build date: {BUILD_DATE}\n\
+         Target: {BUILD_TARGET}\n\
+         Features: {}\n\
+         Sudoers path: {}",
+        features().join(" "),
+        sudoers_path.display()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_info_shows_sudoers_path() {
+        let info = build_info(Path::new("/etc/sudoers-rs"));
+        assert!(info.ends_with("\nSudoers path: /etc/sudoers-rs"));
+    }
+
+    #[test]
+    fn build_info_is_derived_from_the_build() {
+        let info = build_info(Path::new("/etc/sudoers"));
+        assert!(info.starts_with(&format!("Build date: {BUILD_DATE}\n")));
+        assert!(info.contains("\nFeatures: PAM"));
+    }
+}
@@ -5,16 +5,16 @@ use std::{
     ffi::{CStr, CString, OsString},
     fs::{File, Permissions},
     io::{self, Read, Seek, Write},
-    os::unix::prelude::{MetadataExt, OsStringExt, PermissionsExt},
+    os::unix::prelude::{AsRawFd, MetadataExt, OsStringExt, PermissionsExt},
     path::{Path, PathBuf},
     process::Command,
 };
 
 use crate::{
-    sudoers::Sudoers,
+    sudoers::{Sudoers, SUDOERS_PATH},
     system::{
-        can_execute,
         file::{Chown, FileLock},
+        is_same_file,
         signal::{consts::*, register_handlers, SignalStream},
         User,
     },
@@ -63,7 +63,7 @@ pub fn main() {
 }
 
 fn check(file_arg: Option<&str>, perms: bool, owner: bool) -> io::Result<()> {
-    let sudoers_path = Path::new(file_arg.unwrap_or("/etc/sudoers"));
+    let sudoers_path = Path::new(file_arg.unwrap_or(SUDOERS_PATH));
 
     let sudoers_file = File::open(sudoers_path)
         .map_err(|err| io_msg!(err, "unable to open {}", sudoers_path.display()))?;
@@ -115,7 +115,7 @@ fn check(file_arg: Option<&str>, perms: bool, owner: bool) -> io::Result<()> {
 }
 
 fn run(file_arg: Option<&str>, perms: bool, owner: bool) -> io::Result<()> {
-    let sudoers_path = Path::new(file_arg.unwrap_or("/etc/sudoers"));
+    let sudoers_path = Path::new(file_arg.unwrap_or(SUDOERS_PATH));
 
     let (sudoers_file, existed) = if sudoers_path.exists() {
         let file = File::options().read(true).write(true).open(sudoers_path)?;
@@ -140,11 +140,16 @@ fn run(file_arg: Option<&str>, perms: bool, owner: bool) -> io::Result<()> {
         }
     })?;
 
-    if perms || file_arg.is_none() {
+    // editing the system sudoers file using a different path (e.g. a symlink to it) should not
+    // allow its permissions and ownership to be left as they are
+    let is_system_sudoers =
+        file_arg.is_none() || is_same_file(sudoers_path, Path::new(SUDOERS_PATH)).unwrap_or(false);
+
+    if perms || is_system_sudoers {
         sudoers_file.set_permissions(Permissions::from_mode(0o440))?;
     }
 
-    if owner || file_arg.is_none() {
+    if owner || is_system_sudoers {
         sudoers_file.chown(User::real_uid(), User::real_gid())?;
     }
 
@@ -198,7 +203,7 @@ fn edit_sudoers_file(
     mut tmp_file: File,
     tmp_path: &Path,
 ) -> io::Result<()> {
-    let mut editor_path = None;
+    let mut sudoers = None;
     let mut sudoers_contents = Vec::new();
     if existed {
         // If the sudoers file existed, read its contents and write them into the temporary file.
@@ -208,17 +213,15 @@ fn edit_sudoers_file(
         // Write to the temporary file.
         tmp_file.write_all(&sudoers_contents)?;
 
-        let (sudoers, errors) = Sudoers::read(sudoers_contents.as_slice(), sudoers_path)?;
+        let (existing, errors) = Sudoers::read(sudoers_contents.as_slice(), sudoers_path)?;
 
         if errors.is_empty() {
-            editor_path = sudoers.solve_editor_path();
+            sudoers = Some(existing);
         }
     }
 
-    let editor_path = match editor_path {
-        Some(path) => path,
-        None => editor_path_fallback()?,
-    };
+    // a missing or invalid sudoers file does not configure an editor, use the default settings
+    let editor_path = sudoers.unwrap_or_default().solve_editor_path()?;
 
     let mut stderr = io::stderr();
     loop {
@@ -281,6 +284,19 @@ fn edit_sudoers_file(
     if tmp_contents == sudoers_contents {
         writeln!(stderr, "visudo: {} unchanged", tmp_path.display())?;
     } else {
+        // the sudoers file may have been replaced (e.g. renamed over) while it was being edited,
+        // writing to the file that was opened would then silently discard the changes
+        let opened_file = PathBuf::from(format!("/proc/self/fd/{}", sudoers_file.as_raw_fd()));
+        if !is_same_file(sudoers_path, &opened_file)? {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                format!(
+                    "{} was replaced while it was being edited, changes discarded",
+                    sudoers_path.display()
+                ),
+            ));
+        }
+
         sudoers_file.write_all(&tmp_contents)?;
     }
 
@@ -289,18 +305,6 @@ fn edit_sudoers_file(
     Ok(())
 }
 
-fn editor_path_fallback() -> io::Result<PathBuf> {
-    let path = Path::new("/usr/bin/editor");
-    if can_execute(path) {
-        return Ok(path.to_owned());
-    }
-
-    Err(io::Error::new(
-        io::ErrorKind::NotFound,
-        "cannot find text editor",
-    ))
-}
-
 macro_rules! cstr {
     ($expr:expr) => {{
         let _: &'static [u8] = $expr;
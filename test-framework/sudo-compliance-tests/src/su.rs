@@ -1,6 +1,6 @@
-use sudo_test::{Command, Env, User};
+use sudo_test::{Command, Directory, Env, User};
 
-use crate::{Result, PANIC_EXIT_CODE, PASSWORD, USERNAME};
+use crate::{Result, OTHER_USERNAME, PANIC_EXIT_CODE, PASSWORD, USERNAME};
 
 mod cli;
 mod env;
@@ -166,3 +166,25 @@ fn does_not_panic_on_io_errors() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn keeps_inaccessible_working_directory() -> Result<()> {
+    // unlike sudo, su does not move away from a working directory the target user cannot access
+    let private_dir = "/tmp/private";
+    let env = Env("")
+        .user(USERNAME)
+        .user(User(OTHER_USERNAME).create_home_directory())
+        .directory(Directory(private_dir).chown(USERNAME).chmod("700"))
+        .build()?;
+
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg(format!("cd {private_dir}; su {OTHER_USERNAME} -c /bin/pwd"))
+        .output(&env)?;
+
+    assert!(output.status().success(), "{}", output.stderr());
+    assert_not_contains!(output.stderr(), "unable to change directory");
+    assert_eq!(private_dir, output.stdout()?);
+
+    Ok(())
+}
@@ -1,12 +1,15 @@
 mod child_process;
 mod cli;
 mod env_reset;
+mod flag_bell;
 mod flag_chdir;
 mod flag_group;
 mod flag_help;
 mod flag_list;
+mod flag_list_defaults;
 mod flag_login;
 mod flag_non_interactive;
+mod flag_preserve_env;
 mod flag_shell;
 mod flag_user;
 mod flag_version;
@@ -22,6 +25,7 @@ mod password_retry;
 mod path_search;
 mod perms;
 mod sudo_ps1;
+mod sudoedit;
 mod sudoers;
 mod syslog;
 mod timestamp;
@@ -19,6 +19,52 @@ fn sudo_forwards_childs_exit_code() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn sudo_forwards_childs_exit_code_for_common_values() -> Result<()> {
+    let env = Env(SUDOERS_ROOT_ALL_NOPASSWD).build()?;
+
+    for expected in [0, 1, 42, 127] {
+        let output = Command::new("sudo")
+            .args(["sh", "-c"])
+            .arg(format!("exit {expected}"))
+            .output(&env)?;
+        assert_eq!(Some(expected), output.status().code());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn sudo_forwards_childs_exit_code_when_using_a_tty() -> Result<()> {
+    let env = Env(SUDOERS_ROOT_ALL_NOPASSWD).build()?;
+
+    for expected in [0, 1, 42, 127] {
+        let output = Command::new("sudo")
+            .args(["sh", "-c"])
+            .arg(format!("exit {expected}"))
+            .tty(true)
+            .output(&env)?;
+        assert_eq!(Some(expected), output.status().code());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn sudo_reraises_signal_that_killed_child() -> Result<()> {
+    let env = Env(SUDOERS_ROOT_ALL_NOPASSWD).build()?;
+
+    // sudo should die from the same signal, which the shell reports as 128 + SIGTERM (15)
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg("sudo sh -c 'kill -TERM $$'; echo $?")
+        .output(&env)?;
+
+    assert_eq!("143", output.stdout()?);
+
+    Ok(())
+}
+
 #[test]
 fn sudo_forwards_childs_stdout() -> Result<()> {
     let env = Env(SUDOERS_ROOT_ALL_NOPASSWD).build()?;
@@ -46,6 +92,63 @@ fn sudo_forwards_childs_stderr() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn sudo_forwards_childs_stderr_and_exit_code() -> Result<()> {
+    let env = Env(SUDOERS_ROOT_ALL_NOPASSWD).build()?;
+
+    let output = Command::new("sudo")
+        .args(["sh", "-c", "echo error >&2; exit 1"])
+        .output(&env)?;
+
+    assert_eq!(Some(1), output.status().code());
+    assert_contains!(output.stderr(), "error");
+
+    Ok(())
+}
+
+#[test]
+fn sudo_forwards_childs_stderr_when_using_a_tty() -> Result<()> {
+    let env = Env(SUDOERS_ROOT_ALL_NOPASSWD).build()?;
+
+    let output = Command::new("sudo")
+        .args(["sh", "-c", "echo error >&2; exit 1"])
+        .tty(true)
+        .output(&env)?;
+
+    assert_eq!(Some(1), output.status().code());
+    // Docker merges stderr into stdout with "--tty". See gh622
+    assert_contains!(output.stdout_unchecked(), "error");
+
+    Ok(())
+}
+
+#[test]
+#[ignore = "log_output"]
+fn sudo_forwards_childs_stderr_with_io_logging() -> Result<()> {
+    let env = Env([
+        "Defaults log_output",
+        "Defaults !compress_io",
+        SUDOERS_ROOT_ALL_NOPASSWD,
+    ])
+    .build()?;
+
+    let output = Command::new("sudo")
+        .args(["sh", "-c", "echo error >&2; exit 1"])
+        .output(&env)?;
+
+    assert_eq!(Some(1), output.status().code());
+    assert_contains!(output.stderr(), "error");
+
+    let logged = Command::new("sh")
+        .args(["-c", "find /var/log/sudo-io -name stderr -exec cat {} +"])
+        .output(&env)?
+        .stdout()?;
+
+    assert_contains!(logged, "error");
+
+    Ok(())
+}
+
 #[test]
 fn sudo_forwards_stdin_to_child() -> Result<()> {
     let expected = "hello";
@@ -1,3 +1,5 @@
+use std::time::{Duration, Instant};
+
 use pretty_assertions::assert_eq;
 use sudo_test::{Command, Env};
 
@@ -39,6 +41,7 @@ dup! {
     sigtstp_works,
     sigalrm_terminates_command,
     sigchld_is_ignored,
+    sigpipe_terminates_command,
 }
 
 // man sudo > Signal handling
@@ -216,6 +219,30 @@ fn sigchld_is_ignored(tty: bool) -> Result<()> {
     Ok(())
 }
 
+// the reader of the pipe exits after the first line; the command should then be killed by SIGPIPE
+// and sudo should exit promptly, reporting that signal in its exit status
+fn sigpipe_terminates_command(tty: bool) -> Result<()> {
+    let env = Env([SUDOERS_ALL_ALL_NOPASSWD, SUDOERS_USE_PTY]).build()?;
+
+    let start = Instant::now();
+    let output = Command::new("bash")
+        .args([
+            "-c",
+            "timeout 10 sudo yes | head -n 1; echo ${PIPESTATUS[0]}",
+        ])
+        .tty(tty)
+        .output(&env)?;
+    let elapsed = start.elapsed();
+
+    assert!(output.status().success(), "{}", output.stderr());
+    let stdout = output.stdout()?;
+    let lines = stdout.lines().map(str::trim_end).collect::<Vec<_>>();
+    assert_eq!(["y", "141"], &lines[..]);
+    assert!(elapsed < Duration::from_secs(8), "sudo ran for {elapsed:?}");
+
+    Ok(())
+}
+
 fn sigwinch_works(use_pty: bool) -> Result<()> {
     let print_sizes = "/root/print-sizes.sh";
     let change_size = "/root/change-size.sh";
@@ -138,3 +138,121 @@ fn dash_dash_flag_equal_value_syntax() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn no_arguments_prints_usage() -> Result<()> {
+    let env = Env(SUDOERS_ALL_ALL_NOPASSWD).build()?;
+
+    let output = Command::new("sudo").output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+    assert_contains!(output.stderr(), "usage: sudo -h | -K | -k | -V");
+
+    Ok(())
+}
+
+#[test]
+fn just_dash_dash_without_command_prints_usage() -> Result<()> {
+    let env = Env(SUDOERS_ALL_ALL_NOPASSWD).build()?;
+
+    let output = Command::new("sudo").arg("--").output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+    assert_contains!(output.stderr(), "usage: sudo -h | -K | -k | -V");
+
+    Ok(())
+}
+
+#[test]
+fn user_flag_without_command_prints_usage() -> Result<()> {
+    let env = Env(SUDOERS_ALL_ALL_NOPASSWD).build()?;
+
+    let output = Command::new("sudo").args(["-u", "root"]).output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+    assert_contains!(output.stderr(), "usage: sudo -h | -K | -k | -V");
+
+    Ok(())
+}
+
+#[test]
+fn argument_with_spaces_is_passed_as_is() -> Result<()> {
+    let env = Env(SUDOERS_ALL_ALL_NOPASSWD).build()?;
+
+    let output = Command::new("sudo")
+        .args([
+            "sh",
+            "-c",
+            "printf '[%s]' \"$@\"",
+            "sh",
+            "hello world",
+            "  padded  ",
+        ])
+        .output(&env)?;
+
+    assert_eq!("[hello world][  padded  ]", output.stdout()?);
+
+    Ok(())
+}
+
+#[test]
+fn argument_with_newlines_is_passed_as_is() -> Result<()> {
+    let env = Env(SUDOERS_ALL_ALL_NOPASSWD).build()?;
+
+    let output = Command::new("sudo")
+        .args(["sh", "-c", "printf '[%s]' \"$@\"", "sh", "first\nsecond"])
+        .output(&env)?;
+
+    assert_eq!("[first\nsecond]", output.stdout()?);
+
+    Ok(())
+}
+
+#[test]
+fn argument_with_null_byte_is_handled_gracefully() -> Result<()> {
+    let env = Env(SUDOERS_ALL_ALL_NOPASSWD).build()?;
+
+    // arguments are C strings so the shell can only pass what comes before the null byte
+    let output = Command::new("bash")
+        .args(["-c", "sudo echo $'before\\0after'"])
+        .output(&env)?;
+
+    assert!(output.status().success());
+    assert_eq!("before", output.stdout()?);
+
+    Ok(())
+}
+
+#[test]
+fn flags_after_command_are_passed_to_command() -> Result<()> {
+    let env = Env(SUDOERS_ALL_ALL_NOPASSWD).build()?;
+
+    let output = Command::new("sudo").args(["echo", "--help"]).output(&env)?;
+
+    assert!(output.status().success());
+    assert_eq!("--help", output.stdout()?);
+
+    Ok(())
+}
+
+#[test]
+fn flag_after_dash_dash_is_the_command() -> Result<()> {
+    let env = Env(SUDOERS_ALL_ALL_NOPASSWD).build()?;
+
+    let output = Command::new("sudo").args(["--", "--login"]).output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+
+    let diagnostic = if sudo_test::is_original_sudo() {
+        "--login: command not found"
+    } else {
+        "'--login': command not found"
+    };
+    assert_contains!(output.stderr(), diagnostic);
+
+    Ok(())
+}
@@ -0,0 +1,110 @@
+//! `-B` / `--bell` and `Defaults bell`: ring the terminal bell when authentication fails
+
+use sudo_test::{Command, Env, User};
+
+use crate::{Result, PASSWORD, USERNAME};
+
+const BELL: char = '\u{7}';
+
+fn assert_bell_on_failure(env: &Env, sudo_args: &str) -> Result<()> {
+    // `script` runs sudo on a pseudo terminal, so the password is prompted for on a terminal;
+    // everything written to that terminal, including the bell, ends up in its stdout
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg(format!(
+            "printf 'wrong-password\\n{PASSWORD}\\n' | script -qec 'sudo {sudo_args} true' /dev/null"
+        ))
+        .as_user(USERNAME)
+        .output(env)?;
+
+    assert!(output.status().success(), "{}", output.stderr());
+    assert_contains!(output.stdout()?, BELL.to_string());
+
+    Ok(())
+}
+
+#[test]
+fn short_flag_rings_bell_on_failed_attempt() -> Result<()> {
+    let env = Env(format!("{USERNAME} ALL=(ALL:ALL) ALL"))
+        .user(User(USERNAME).password(PASSWORD))
+        .build()?;
+
+    assert_bell_on_failure(&env, "-B")
+}
+
+#[test]
+fn long_flag_rings_bell_on_failed_attempt() -> Result<()> {
+    let env = Env(format!("{USERNAME} ALL=(ALL:ALL) ALL"))
+        .user(User(USERNAME).password(PASSWORD))
+        .build()?;
+
+    assert_bell_on_failure(&env, "--bell")
+}
+
+#[test]
+fn default_rings_bell_on_failed_attempt() -> Result<()> {
+    let env = Env(format!("Defaults bell\n{USERNAME} ALL=(ALL:ALL) ALL"))
+        .user(User(USERNAME).password(PASSWORD))
+        .build()?;
+
+    assert_bell_on_failure(&env, "")
+}
+
+#[test]
+fn no_bell_by_default() -> Result<()> {
+    let env = Env(format!("{USERNAME} ALL=(ALL:ALL) ALL"))
+        .user(User(USERNAME).password(PASSWORD))
+        .build()?;
+
+    // see `assert_bell_on_failure`
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg(format!(
+            "printf 'wrong-password\\n{PASSWORD}\\n' | script -qec 'sudo true' /dev/null"
+        ))
+        .as_user(USERNAME)
+        .output(&env)?;
+
+    assert!(output.status().success(), "{}", output.stderr());
+    assert_not_contains!(output.stdout()?, BELL.to_string());
+
+    Ok(())
+}
+
+#[test]
+fn no_bell_when_reading_password_from_stdin() -> Result<()> {
+    let env = Env(format!("{USERNAME} ALL=(ALL:ALL) ALL"))
+        .user(User(USERNAME).password(PASSWORD))
+        .build()?;
+
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg(format!(
+            "(echo wrong-password; echo {PASSWORD}) | sudo -S -B true"
+        ))
+        .as_user(USERNAME)
+        .output(&env)?;
+
+    assert!(output.status().success(), "{}", output.stderr());
+    assert_not_contains!(output.stderr(), BELL.to_string());
+
+    Ok(())
+}
+
+#[test]
+fn no_bell_on_successful_attempt() -> Result<()> {
+    let env = Env(format!("{USERNAME} ALL=(ALL:ALL) ALL"))
+        .user(User(USERNAME).password(PASSWORD))
+        .build()?;
+
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg(format!("echo {PASSWORD} | sudo -S -B true"))
+        .as_user(USERNAME)
+        .output(&env)?;
+
+    assert!(output.status().success(), "{}", output.stderr());
+    assert_not_contains!(output.stderr(), BELL.to_string());
+
+    Ok(())
+}
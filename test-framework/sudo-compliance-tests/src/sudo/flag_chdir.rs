@@ -33,6 +33,48 @@ fn cwd_set_to_glob_change_dir() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn short_flag_changes_dir() -> Result<()> {
+    let env = Env(TextFile("ALL ALL=(ALL:ALL) CWD=* NOPASSWD: ALL")).build()?;
+    let output = Command::new("sh")
+        .args(["-c", "cd /; sudo -D /root pwd"])
+        .output(&env)?;
+
+    assert!(output.status().success(), "{}", output.stderr());
+    assert_eq!("/root", output.stdout()?);
+
+    Ok(())
+}
+
+#[test]
+fn long_flag_with_equals_sign_changes_dir() -> Result<()> {
+    let env = Env(TextFile("ALL ALL=(ALL:ALL) CWD=* NOPASSWD: ALL")).build()?;
+    let output = Command::new("sh")
+        .args(["-c", "cd /; sudo --chdir=/root pwd"])
+        .output(&env)?;
+
+    assert!(output.status().success(), "{}", output.stderr());
+    assert_eq!("/root", output.stdout()?);
+
+    Ok(())
+}
+
+#[test]
+fn target_user_can_change_to_accessible_dir() -> Result<()> {
+    let env = Env(TextFile("ALL ALL=(ALL:ALL) CWD=* NOPASSWD: ALL"))
+        .user(USERNAME)
+        .build()?;
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg(format!("cd /; sudo -u {USERNAME} --chdir /tmp pwd"))
+        .output(&env)?;
+
+    assert!(output.status().success(), "{}", output.stderr());
+    assert_eq!("/tmp", output.stdout()?);
+
+    Ok(())
+}
+
 #[test]
 fn cwd_fails_for_non_existent_dirs() -> Result<()> {
     let env = Env(TextFile("ALL ALL=(ALL:ALL) CWD=* NOPASSWD: ALL")).build()?;
@@ -57,6 +99,23 @@ fn cwd_fails_for_non_existent_dirs() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn cwd_fails_for_files() -> Result<()> {
+    let env = Env(TextFile("ALL ALL=(ALL:ALL) CWD=* NOPASSWD: ALL")).build()?;
+    let output = Command::new("sudo")
+        .args(["--chdir", "/etc/passwd", "sh", "-c", "echo >&2 'avocado'"])
+        .output(&env)?;
+    assert_eq!(Some(1), output.status().code());
+    let stderr = output.stderr();
+    assert_contains!(
+        stderr,
+        "unable to change directory to /etc/passwd: Not a directory"
+    );
+    assert_not_contains!(stderr, "avocado");
+
+    Ok(())
+}
+
 #[test]
 fn cwd_with_login_fails_for_non_existent_dirs() -> Result<()> {
     let env = Env(TextFile("ALL ALL=(ALL:ALL) CWD=* NOPASSWD: ALL"))
@@ -3,6 +3,7 @@ use sudo_test::{Command, Env, TextFile, User};
 use crate::{Result, PANIC_EXIT_CODE, PASSWORD, SUDOERS_ALL_ALL_NOPASSWD, USERNAME};
 
 mod credential_caching;
+mod flag_host;
 mod flag_other_user;
 mod long_format;
 mod needs_auth;
@@ -203,6 +204,60 @@ fn fails_when_user_is_not_allowed_in_sudoers_no_command() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn fails_when_no_rule_matches_user() -> Result<()> {
+    let other_user = "ghost";
+    let hostname = "container";
+    let env = Env(format!(
+        "root ALL=(ALL:ALL) ALL\n{other_user} ALL=(ALL:ALL) ALL\n%users ALL=(ALL:ALL) ALL"
+    ))
+    .user(User(USERNAME).password(PASSWORD))
+    .user(other_user)
+    .hostname(hostname)
+    .build()?;
+
+    let output = Command::new("sudo")
+        .args(["-S", "-l"])
+        .as_user(USERNAME)
+        .stdin(PASSWORD)
+        .output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+    assert!(output.stdout_unchecked().is_empty());
+
+    let diagnostic = format!("Sorry, user {USERNAME} may not run sudo on {hostname}.");
+    assert_contains!(output.stderr(), diagnostic);
+
+    Ok(())
+}
+
+#[test]
+fn lists_all_privileges_that_require_a_password() -> Result<()> {
+    let hostname = "container";
+    let env = Env(format!("{USERNAME} ALL=(ALL:ALL) ALL"))
+        .user(User(USERNAME).password(PASSWORD))
+        .hostname(hostname)
+        .build()?;
+
+    let output = Command::new("sudo")
+        .args(["-S", "-l"])
+        .as_user(USERNAME)
+        .stdin(PASSWORD)
+        .output(&env)?;
+
+    assert!(output.status().success(), "{}", output.stderr());
+
+    let expected = format!(
+        "User {USERNAME} may run the following commands on {hostname}:
+    (ALL : ALL) ALL"
+    );
+    let actual = output.stdout()?;
+    assert_eq!(actual, expected);
+
+    Ok(())
+}
+
 #[test]
 fn does_not_work_with_lowercase_u_flag() -> Result<()> {
     let hostname = "container";
@@ -0,0 +1,135 @@
+use sudo_test::{Command, Env};
+
+use crate::{Result, USERNAME};
+
+#[test]
+fn lists_privileges_of_other_user_on_specified_host() -> Result<()> {
+    let hostname = "container";
+    let other_host = "remotehost";
+    let env = Env(format!("{USERNAME} {other_host} = (ALL:ALL) NOPASSWD: ALL"))
+        .user(USERNAME)
+        .hostname(hostname)
+        .build()?;
+
+    let output = Command::new("sudo")
+        .args(["-l", "-U", USERNAME, "-h", other_host])
+        .output(&env)?;
+
+    assert!(output.status().success());
+    assert!(output.stderr().is_empty());
+
+    let expected = format!(
+        "User {USERNAME} may run the following commands on {other_host}:
+    (ALL : ALL) NOPASSWD: ALL"
+    );
+    let actual = output.stdout()?;
+    assert_eq!(actual, expected);
+
+    Ok(())
+}
+
+#[test]
+fn long_form_works() -> Result<()> {
+    let hostname = "container";
+    let other_host = "remotehost";
+    let env = Env(format!("{USERNAME} {other_host} = (ALL:ALL) NOPASSWD: ALL"))
+        .user(USERNAME)
+        .hostname(hostname)
+        .build()?;
+
+    let output = Command::new("sudo")
+        .args(["-l", "-U", USERNAME, &format!("--host={other_host}")])
+        .output(&env)?;
+
+    assert!(output.status().success());
+
+    let expected = format!("User {USERNAME} may run the following commands on {other_host}:");
+    assert_contains!(output.stdout()?, expected);
+
+    Ok(())
+}
+
+#[test]
+fn rules_for_the_current_host_do_not_apply_to_the_specified_host() -> Result<()> {
+    let hostname = "container";
+    let other_host = "remotehost";
+    let env = Env(format!("{USERNAME} {hostname} = (ALL:ALL) NOPASSWD: ALL"))
+        .user(USERNAME)
+        .hostname(hostname)
+        .build()?;
+
+    let output = Command::new("sudo")
+        .args(["-l", "-U", USERNAME, "-h", other_host])
+        .output(&env)?;
+
+    assert!(output.status().success());
+
+    let expected = format!("User {USERNAME} is not allowed to run sudo on {other_host}.");
+    let actual = output.stdout()?;
+    assert_eq!(actual, expected);
+
+    Ok(())
+}
+
+#[test]
+fn regular_user_cannot_use_host_flag() -> Result<()> {
+    if sudo_test::is_original_sudo() {
+        // original sudo lets any user combine `--host` with `--list`
+        return Ok(());
+    }
+
+    let other_host = "remotehost";
+    let env = Env("ALL ALL=(ALL:ALL) NOPASSWD: ALL")
+        .user(USERNAME)
+        .build()?;
+
+    let output = Command::new("sudo")
+        .args(["-l", "-h", other_host])
+        .as_user(USERNAME)
+        .output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+    assert_contains!(output.stderr(), "only root can use '--host'");
+
+    Ok(())
+}
+
+#[test]
+fn cannot_be_used_without_list() -> Result<()> {
+    let env = Env("ALL ALL=(ALL:ALL) NOPASSWD: ALL").build()?;
+
+    let output = Command::new("sudo")
+        .args(["-h", "remotehost", "true"])
+        .output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+
+    Ok(())
+}
+
+#[test]
+fn ip_addresses_of_the_current_host_do_not_apply_to_the_specified_host() -> Result<()> {
+    if sudo_test::is_original_sudo() {
+        // original sudo matches IP addresses against the local interfaces, even with `--host`
+        return Ok(());
+    }
+
+    let other_host = "remotehost";
+    let env = Env(format!("{USERNAME} 0.0.0.0/0 = (ALL:ALL) NOPASSWD: ALL"))
+        .user(USERNAME)
+        .build()?;
+
+    let output = Command::new("sudo")
+        .args(["-l", "-U", USERNAME, "-h", other_host])
+        .output(&env)?;
+
+    assert!(output.status().success());
+
+    let expected = format!("User {USERNAME} is not allowed to run sudo on {other_host}.");
+    let actual = output.stdout()?;
+    assert_eq!(actual, expected);
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use sudo_test::{Command, Env};
+
+use crate::{Result, USERNAME};
+
+#[test]
+fn lists_known_defaults_options() -> Result<()> {
+    if sudo_test::is_original_sudo() {
+        // `--list-defaults` is an extension of sudo-rs
+        return Ok(());
+    }
+
+    let env = Env("").user(USERNAME).build()?;
+
+    // no permissions are needed to list the available options
+    let output = Command::new("sudo")
+        .arg("--list-defaults")
+        .as_user(USERNAME)
+        .output(&env)?;
+
+    assert!(output.status().success(), "{}", output.stderr());
+
+    let stdout = output.stdout()?;
+    for (name, kind) in [
+        ("env_reset", "boolean"),
+        ("passwd_tries", "integer"),
+        ("secure_path", "string"),
+        ("env_keep", "list"),
+    ] {
+        assert!(
+            stdout
+                .lines()
+                .any(|line| line.split_whitespace().take(2).eq([name, kind])),
+            "{name} ({kind}) is missing from:\n{stdout}"
+        );
+    }
+
+    // the value is shown in the units used in sudoers, i.e. minutes
+    assert!(
+        stdout.lines().any(|line| line.split_whitespace().eq([
+            "timestamp_timeout",
+            "integer",
+            "15"
+        ])),
+        "{stdout}"
+    );
+
+    Ok(())
+}
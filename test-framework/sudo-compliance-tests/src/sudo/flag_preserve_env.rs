@@ -0,0 +1,81 @@
+use pretty_assertions::assert_eq;
+use sudo_test::{Command, Env};
+
+use crate::{helpers, Result, SUDOERS_ALL_ALL_NOPASSWD};
+
+const HOME: &str = "/tmp/home";
+
+#[test]
+#[ignore = "preserve-env list"]
+fn preserves_single_variable() -> Result<()> {
+    let env = Env(SUDOERS_ALL_ALL_NOPASSWD).build()?;
+
+    let stdout = Command::new("env")
+        .args([
+            &format!("HOME={HOME}"),
+            "FOO=bar",
+            "sudo",
+            "--preserve-env=HOME",
+            "/usr/bin/env",
+        ])
+        .output(&env)?
+        .stdout()?;
+    let sudo_env = helpers::parse_env_output(&stdout)?;
+
+    // not overridden by the home directory of the target user
+    assert_eq!(Some(&HOME), sudo_env.get("HOME"));
+    // variables that were not listed are still removed
+    assert_eq!(None, sudo_env.get("FOO"));
+
+    Ok(())
+}
+
+#[test]
+#[ignore = "preserve-env list"]
+fn preserves_several_variables() -> Result<()> {
+    let display = ":42";
+    let env = Env(SUDOERS_ALL_ALL_NOPASSWD).build()?;
+
+    let stdout = Command::new("env")
+        .args([
+            &format!("HOME={HOME}"),
+            &format!("DISPLAY={display}"),
+            "FOO=bar",
+            "sudo",
+            "--preserve-env=HOME,DISPLAY",
+            "/usr/bin/env",
+        ])
+        .output(&env)?
+        .stdout()?;
+    let sudo_env = helpers::parse_env_output(&stdout)?;
+
+    assert_eq!(Some(&HOME), sudo_env.get("HOME"));
+    assert_eq!(Some(&display), sudo_env.get("DISPLAY"));
+    assert_eq!(None, sudo_env.get("FOO"));
+
+    Ok(())
+}
+
+#[test]
+#[ignore = "preserve-env list"]
+fn unsafe_variable_is_denied_without_setenv() -> Result<()> {
+    let env = Env("ALL ALL=(ALL:ALL) NOPASSWD: /usr/bin/env").build()?;
+
+    let output = Command::new("env")
+        .args([
+            "LD_PRELOAD=/tmp/evil.so",
+            "sudo",
+            "--preserve-env=LD_PRELOAD",
+            "/usr/bin/env",
+        ])
+        .output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+    assert_contains!(
+        output.stderr(),
+        "sorry, you are not allowed to set the following environment variables: LD_PRELOAD"
+    );
+
+    Ok(())
+}
@@ -19,3 +19,43 @@ fn does_not_panic_on_io_errors() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn shows_build_information() -> Result<()> {
+    let env = Env("").build()?;
+
+    let output = Command::new("sudo").arg("--version").output(&env)?;
+
+    assert!(output.status().success());
+
+    // sudo-rs prints the version information to stderr
+    let info = if sudo_test::is_original_sudo() {
+        output.stdout()?
+    } else {
+        output.stderr().to_string()
+    };
+
+    // ogsudo lists "Authentication methods: 'pam'"
+    assert_contains!(info.to_uppercase(), "PAM");
+    assert_contains!(info, "/etc/sudoers");
+
+    Ok(())
+}
+
+#[test]
+fn shows_the_sudoers_file_in_use() -> Result<()> {
+    if sudo_test::is_original_sudo() {
+        // ogsudo does not read /etc/sudoers-rs
+        return Ok(());
+    }
+
+    let env = Env("").file("/etc/sudoers-rs", "").build()?;
+
+    let output = Command::new("sudo").arg("--version").output(&env)?;
+
+    assert!(output.status().success());
+    assert_contains!(output.stderr(), "Build date: ");
+    assert_contains!(output.stderr(), "Sudoers path: /etc/sudoers-rs");
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use crate::{
     Result, OG_SUDO_STANDARD_LECTURE, PASSWORD, SUDOERS_NEW_LECTURE, SUDOERS_NEW_LECTURE_USER,
-    SUDOERS_ONCE_LECTURE, SUDOERS_ROOT_ALL, USERNAME,
+    SUDOERS_ONCE_LECTURE, SUDOERS_ROOT_ALL, SUDOERS_USER_ALL_ALL, USERNAME,
 };
 use sudo_test::{Command, Env, User};
 
@@ -86,3 +86,63 @@ fn default_lecture_for_unspecified_user() -> Result<()> {
     assert_contains!(output.stderr(), OG_SUDO_STANDARD_LECTURE);
     Ok(())
 }
+
+#[ignore = "gh400"]
+#[test]
+fn new_lecture_message_is_shown_again_after_removing_lecture_marker() -> Result<()> {
+    let new_lecture = "I <3 sudo";
+    let env = Env([
+        SUDOERS_USER_ALL_ALL,
+        SUDOERS_ONCE_LECTURE,
+        SUDOERS_NEW_LECTURE,
+    ])
+    .file("/etc/sudo_lecture", new_lecture)
+    .user(User(USERNAME).password(PASSWORD))
+    .build()?;
+
+    let sudo = || {
+        Command::new("sudo")
+            .as_user(USERNAME)
+            .stdin(PASSWORD)
+            .args(["-k", "-S", "true"])
+            .output(&env)
+    };
+
+    let output = sudo()?;
+    assert!(output.status().success(), "{}", output.stderr());
+    assert_contains!(output.stderr(), new_lecture);
+
+    let output = sudo()?;
+    assert!(output.status().success(), "{}", output.stderr());
+    assert_not_contains!(output.stderr(), new_lecture);
+
+    Command::new("rm")
+        .args(["-rf", &format!("/var/lib/sudo/lectured/{USERNAME}")])
+        .output(&env)?
+        .assert_success()?;
+
+    let output = sudo()?;
+    assert!(output.status().success(), "{}", output.stderr());
+    assert_contains!(output.stderr(), new_lecture);
+
+    Ok(())
+}
+
+#[ignore = "gh399"]
+#[test]
+fn default_lecture_asks_for_respect() -> Result<()> {
+    let env = Env([SUDOERS_USER_ALL_ALL, SUDOERS_ONCE_LECTURE])
+        .user(User(USERNAME).password(PASSWORD))
+        .build()?;
+
+    let output = Command::new("sudo")
+        .args(["-S", "true"])
+        .as_user(USERNAME)
+        .stdin(PASSWORD)
+        .output(&env)?;
+
+    assert!(output.status().success(), "{}", output.stderr());
+    assert_contains!(output.stderr().to_lowercase(), "respect");
+
+    Ok(())
+}
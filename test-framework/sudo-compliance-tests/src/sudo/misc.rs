@@ -1,6 +1,6 @@
-use sudo_test::{Command, Env};
+use sudo_test::{Command, Directory, Env, User};
 
-use crate::{Result, PANIC_EXIT_CODE, SUDOERS_ALL_ALL_NOPASSWD, USERNAME};
+use crate::{Result, OTHER_USERNAME, PANIC_EXIT_CODE, SUDOERS_ALL_ALL_NOPASSWD, USERNAME};
 
 macro_rules! assert_snapshot {
     ($($tt:tt)*) => {
@@ -221,3 +221,70 @@ fn does_not_panic_on_io_errors_cli_error() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn inaccessible_working_directory_falls_back_to_home_directory() -> Result<()> {
+    if sudo_test::is_original_sudo() {
+        // original sudo only warns that it cannot change to the working directory and runs the
+        // command in the inaccessible directory
+        return Ok(());
+    }
+
+    // a directory that the target user cannot access, like a path on an NFS share mounted with
+    // `root_squash` is for root
+    let private_dir = "/tmp/private";
+    let env = Env(SUDOERS_ALL_ALL_NOPASSWD)
+        .user(USERNAME)
+        .user(User(OTHER_USERNAME).create_home_directory())
+        .directory(Directory(private_dir).chown(USERNAME).chmod("700"))
+        .build()?;
+
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg(format!(
+            "cd {private_dir}; sudo -u {OTHER_USERNAME} sh -c 'ls > /dev/null && /bin/pwd'"
+        ))
+        .as_user(USERNAME)
+        .output(&env)?;
+
+    assert!(output.status().success(), "{}", output.stderr());
+    assert_contains!(
+        output.stderr(),
+        format!("unable to change directory to {private_dir}: Permission denied")
+    );
+    assert_eq!(format!("/home/{OTHER_USERNAME}"), output.stdout()?);
+
+    Ok(())
+}
+
+#[test]
+fn inaccessible_working_directory_falls_back_to_root_directory() -> Result<()> {
+    if sudo_test::is_original_sudo() {
+        // see `inaccessible_working_directory_falls_back_to_home_directory`
+        return Ok(());
+    }
+
+    let private_dir = "/tmp/private";
+    let env = Env(SUDOERS_ALL_ALL_NOPASSWD)
+        .user(USERNAME)
+        .user(OTHER_USERNAME)
+        .directory(Directory(private_dir).chown(USERNAME).chmod("700"))
+        .build()?;
+
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg(format!(
+            "cd {private_dir}; sudo -u {OTHER_USERNAME} sh -c 'ls > /dev/null && /bin/pwd'"
+        ))
+        .as_user(USERNAME)
+        .output(&env)?;
+
+    assert!(output.status().success(), "{}", output.stderr());
+    assert_contains!(
+        output.stderr(),
+        format!("unable to change directory to {private_dir}: Permission denied")
+    );
+    assert_eq!("/", output.stdout()?);
+
+    Ok(())
+}
@@ -2,7 +2,7 @@
 
 use sudo_test::{Command, Env, User};
 
-use crate::{Result, GROUPNAME, SUDOERS_NO_LECTURE, SUDOERS_ROOT_ALL, USERNAME};
+use crate::{Result, GROUPNAME, PASSWORD, SUDOERS_NO_LECTURE, SUDOERS_ROOT_ALL, USERNAME};
 
 // NOTE all these tests assume that the invoking user passes the sudoers file 'User_List' criteria
 
@@ -18,6 +18,60 @@ fn user_is_root() -> Result<()> {
         .assert_success()
 }
 
+#[test]
+fn root_as_root_is_not_prompted() -> Result<()> {
+    let env = Env(SUDOERS_ROOT_ALL).build()?;
+
+    let output = Command::new("sudo")
+        .args(["-u", "root", "env"])
+        .output(&env)?;
+
+    assert!(output.status().success(), "{}", output.stderr());
+    assert_not_contains!(output.stderr(), "assword");
+
+    let stdout = output.stdout()?;
+    for var in ["HOME=/root", "USER=root", "SUDO_USER=root", "SUDO_UID=0"] {
+        assert!(stdout.lines().any(|line| line == var), "{var} is not set");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn root_as_other_user_is_not_prompted() -> Result<()> {
+    let env = Env(SUDOERS_ROOT_ALL).user(USERNAME).build()?;
+
+    let output = Command::new("sudo")
+        .args(["-u", USERNAME, "true"])
+        .output(&env)?;
+
+    assert!(output.status().success(), "{}", output.stderr());
+    assert_not_contains!(output.stderr(), "assword");
+
+    Ok(())
+}
+
+// root is exempt from authentication even if the target user's password would be required
+#[test]
+#[ignore = "targetpw"]
+fn root_is_not_prompted_with_targetpw() -> Result<()> {
+    let env = Env(["Defaults targetpw", SUDOERS_ROOT_ALL])
+        .user(User(USERNAME).password(PASSWORD))
+        .build()?;
+
+    for target in ["root", USERNAME] {
+        let output = Command::new("sudo")
+            .args(["-u", target, "true"])
+            .output(&env)?;
+
+        assert!(output.status().success(), "{}", output.stderr());
+        // no password prompt, nor a complaint about the `targetpw` setting
+        assert_eq!("", output.stderr());
+    }
+
+    Ok(())
+}
+
 // man sudoers > User Authentication:
 // "A password is not required if (..) the target user is the same as the invoking user"
 #[test]
@@ -101,3 +101,48 @@ fn sudo_dash_i_uses_correct_service_file() -> Result<()> {
         .output(&env)?
         .assert_success()
 }
+
+// pam_deny.so returns PAM_SESSION_ERR when asked to open a session
+const PAM_SESSION_DENY: &str = "auth sufficient pam_permit.so
+account sufficient pam_permit.so
+session required pam_deny.so";
+
+#[test]
+fn session_open_failure_is_reported() -> Result<()> {
+    let env = Env("ALL ALL=(ALL:ALL) NOPASSWD: ALL")
+        .file("/etc/pam.d/sudo", PAM_SESSION_DENY)
+        .build()?;
+
+    let output = Command::new("sudo").arg("true").output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+    assert_contains!(
+        output.stderr(),
+        "Cannot make/remove an entry for the specified session"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn command_does_not_run_when_session_open_fails() -> Result<()> {
+    let path = "/tmp/command-was-run";
+    let env = Env("ALL ALL=(ALL:ALL) NOPASSWD: ALL")
+        .file("/etc/pam.d/sudo", PAM_SESSION_DENY)
+        .user(USERNAME)
+        .build()?;
+
+    let output = Command::new("sudo")
+        .args(["touch", path])
+        .as_user(USERNAME)
+        .output(&env)?;
+
+    assert!(!output.status().success());
+
+    Command::new("sh")
+        .arg("-c")
+        .arg(format!("test ! -e {path}"))
+        .output(&env)?
+        .assert_success()
+}
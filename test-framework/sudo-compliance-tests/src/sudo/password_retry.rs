@@ -1,6 +1,6 @@
 use sudo_test::{Command, Env, TextFile, User};
 
-use crate::{Result, PASSWORD, USERNAME};
+use crate::{helpers::Rsyslogd, Result, PASSWORD, USERNAME};
 
 #[test]
 fn can_retry_password() -> Result<()> {
@@ -204,3 +204,94 @@ you may want to increase NEW_DELAY_MICROS"
 
     Ok(())
 }
+
+// a PAM stack where authentication always fails; opening a session leaves a marker file
+const SESSION_MARKER: &str = "/tmp/session-opened";
+
+fn pam_always_fails() -> String {
+    format!(
+        "auth requisite pam_deny.so
+account sufficient pam_permit.so
+session optional pam_exec.so /usr/bin/touch {SESSION_MARKER}"
+    )
+}
+
+fn exhaustion_diagnostic(tries: u32) -> String {
+    if sudo_test::is_original_sudo() {
+        format!("{tries} incorrect password attempts")
+    } else {
+        format!("{tries} incorrect authentication attempts")
+    }
+}
+
+#[test]
+fn exhausting_passwd_tries_exits_with_code_1() -> Result<()> {
+    let env = Env(format!(
+        "{USERNAME} ALL=(ALL:ALL) ALL
+Defaults passwd_tries=2"
+    ))
+    .file("/etc/pam.d/sudo", pam_always_fails())
+    .user(User(USERNAME).password(PASSWORD))
+    .build()?;
+
+    let output = Command::new("sudo")
+        .args(["-S", "true"])
+        .as_user(USERNAME)
+        .stdin(PASSWORD)
+        .output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+    assert_contains!(output.stderr(), exhaustion_diagnostic(2));
+
+    Ok(())
+}
+
+#[test]
+fn exhausting_passwd_tries_is_logged() -> Result<()> {
+    let env = Env(format!(
+        "{USERNAME} ALL=(ALL:ALL) ALL
+Defaults passwd_tries=2"
+    ))
+    .file("/etc/pam.d/sudo", pam_always_fails())
+    .user(User(USERNAME).password(PASSWORD))
+    .build()?;
+    let rsyslog = Rsyslogd::start(&env)?;
+
+    let output = Command::new("sudo")
+        .args(["-S", "true"])
+        .as_user(USERNAME)
+        .stdin(PASSWORD)
+        .output(&env)?;
+
+    assert!(!output.status().success());
+
+    let auth_log = rsyslog.auth_log()?;
+    assert_contains!(auth_log, exhaustion_diagnostic(2));
+    assert_contains!(auth_log, "COMMAND=/usr/bin/true");
+
+    Ok(())
+}
+
+#[test]
+fn no_session_is_opened_after_exhausting_passwd_tries() -> Result<()> {
+    let env = Env(format!("{USERNAME} ALL=(ALL:ALL) ALL"))
+        .file("/etc/pam.d/sudo", pam_always_fails())
+        .user(User(USERNAME).password(PASSWORD))
+        .build()?;
+
+    let output = Command::new("sudo")
+        .args(["-S", "true"])
+        .as_user(USERNAME)
+        .stdin(PASSWORD)
+        .output(&env)?;
+
+    assert!(!output.status().success());
+    assert_contains!(output.stderr(), exhaustion_diagnostic(3));
+
+    Command::new("sh")
+        .arg("-c")
+        .arg(format!("test ! -e {SESSION_MARKER}"))
+        .output(&env)?
+        .assert_success()
+}
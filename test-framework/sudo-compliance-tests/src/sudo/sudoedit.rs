@@ -0,0 +1,90 @@
+//! `sudoedit` / `sudo --edit`: editing files as another user through a temporary copy
+
+use sudo_test::{Command, Env, TextFile};
+
+use crate::{Result, USERNAME};
+
+const EDITOR_PATH: &str = "/tmp/editor";
+const NEW_FILE: &str = "/etc/new_file.txt";
+const EDITOR_RAN: &str = "/tmp/editor_ran";
+
+fn env(editor: &str) -> Result<Env> {
+    let env = Env(format!(
+        "{USERNAME} ALL=(ALL:ALL) NOPASSWD: sudoedit {NEW_FILE}"
+    ))
+    .user(USERNAME)
+    .file(EDITOR_PATH, TextFile(editor).chmod("755"))
+    .build()?;
+
+    Ok(env)
+}
+
+#[test]
+#[ignore = "sudoedit"]
+fn creates_new_file_owned_by_root() -> Result<()> {
+    let expected = "hello from the editor";
+    let env = env(&format!(
+        "#!/bin/sh
+for last; do true; done
+echo '{expected}' > \"$last\""
+    ))?;
+
+    Command::new("env")
+        .args([
+            &format!("SUDO_EDITOR={EDITOR_PATH}"),
+            "sudo",
+            "-e",
+            NEW_FILE,
+        ])
+        .as_user(USERNAME)
+        .output(&env)?
+        .assert_success()?;
+
+    let owner = Command::new("stat")
+        .args(["-c", "%U:%G", NEW_FILE])
+        .output(&env)?
+        .stdout()?;
+    assert_eq!("root:root", owner);
+
+    let contents = Command::new("cat").arg(NEW_FILE).output(&env)?.stdout()?;
+    assert_eq!(expected, contents);
+
+    Ok(())
+}
+
+#[test]
+#[ignore = "sudoedit"]
+fn aborted_edit_does_not_create_file() -> Result<()> {
+    let env = env(&format!(
+        "#!/bin/sh
+for last; do true; done
+echo 'discarded' > \"$last\"
+touch {EDITOR_RAN}
+exit 1"
+    ))?;
+
+    let output = Command::new("env")
+        .args([
+            &format!("SUDO_EDITOR={EDITOR_PATH}"),
+            "sudo",
+            "-e",
+            NEW_FILE,
+        ])
+        .as_user(USERNAME)
+        .output(&env)?;
+
+    assert!(!output.status().success());
+
+    // the edit must have been aborted by the editor, not refused by sudo
+    Command::new("test")
+        .args(["-e", EDITOR_RAN])
+        .output(&env)?
+        .assert_success()?;
+
+    Command::new("test")
+        .args(["!", "-e", NEW_FILE])
+        .output(&env)?
+        .assert_success()?;
+
+    Ok(())
+}
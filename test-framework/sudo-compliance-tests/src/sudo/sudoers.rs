@@ -4,17 +4,26 @@ use sudo_test::{Command, Env, TextFile, User};
 
 use crate::{Result, PASSWORD, SUDOERS_ROOT_ALL_NOPASSWD, USERNAME};
 
+mod closefrom_override;
 mod cmnd;
 mod cmnd_alias;
+mod command_timeout;
 mod cwd;
 mod env;
+mod env_file;
 mod host_alias;
 mod host_list;
+mod ignore_unknown_defaults;
 mod include;
 mod includedir;
+mod mail_always;
+mod rootpw;
 mod run_as;
 mod runas_alias;
+mod runas_default;
 mod secure_path;
+mod sudoedit_follow;
+mod targetpw;
 mod timestamp_timeout;
 mod user_list;
 
@@ -45,13 +54,28 @@ const KEYWORDS: &[&str] = &[
     "SETENV",
     "TIMEOUT",
     "User_Alias",
+    "bell",
+    "closefrom_override",
+    "command_timeout",
+    "editor",
     "env_check",
     "env_delete",
     "env_editor",
+    "env_file",
     "env_keep",
+    "fqdn",
+    "ignore_unknown_defaults",
     "include",
     "includedir",
+    "log_allowed",
+    "log_denied",
+    "mail_always",
+    "rootpw",
+    "runas_default",
     "secure_path",
+    "sudoedit_follow",
+    "syslog_maxlen",
+    "syslog_pid",
     "timestamp_timeout",
     "use_pty",
 ];
@@ -135,6 +159,28 @@ fn can_sudo_if_sudoers_file_is_owner_writable() -> Result<()> {
     Ok(())
 }
 
+// only write access is checked by sudo; the mode 0440 is enforced by `visudo --check`
+#[test]
+fn can_sudo_if_sudoers_file_is_world_readable() -> Result<()> {
+    let env = Env(TextFile(SUDOERS_ROOT_ALL_NOPASSWD).chmod("444")).build()?;
+
+    let output = Command::new("sudo").arg("true").output(&env)?;
+    assert_eq!(Some(0), output.status().code(), "{}", output.stderr());
+    assert!(output.stderr().is_empty(), "{}", output.stderr());
+
+    Ok(())
+}
+
+#[test]
+fn can_sudo_if_sudoers_file_is_owner_writable_and_group_readable() -> Result<()> {
+    let env = Env(TextFile(SUDOERS_ROOT_ALL_NOPASSWD).chmod("640")).build()?;
+
+    let output = Command::new("sudo").arg("true").output(&env)?;
+    assert_eq!(Some(0), output.status().code(), "{}", output.stderr());
+
+    Ok(())
+}
+
 #[test]
 fn cannot_sudo_if_sudoers_file_is_not_owned_by_root() -> Result<()> {
     let env = Env(TextFile(SUDOERS_ROOT_ALL_NOPASSWD).chown("1234:root"))
@@ -196,3 +242,129 @@ fn accepts_sudoers_file_that_has_no_trailing_newline() -> Result<()> {
         .output(&env)?
         .assert_success()
 }
+
+#[test]
+fn leading_and_trailing_whitespace_is_ignored() -> Result<()> {
+    let env = Env(format!("   \t{USERNAME} ALL=(ALL:ALL) NOPASSWD: ALL \t  "))
+        .user(User(USERNAME).password(PASSWORD))
+        .build()?;
+
+    Command::new("sudo")
+        .args(["-S", "true"])
+        .as_user(USERNAME)
+        .output(&env)?
+        .assert_success()
+}
+
+#[test]
+fn trailing_comment_is_stripped() -> Result<()> {
+    let env = Env(format!(
+        "{USERNAME} ALL=(ALL:ALL) NOPASSWD: ALL # this comment is not part of the rule"
+    ))
+    .user(User(USERNAME).password(PASSWORD))
+    .build()?;
+
+    Command::new("sudo")
+        .args(["-S", "true"])
+        .as_user(USERNAME)
+        .output(&env)?
+        .assert_success()?;
+
+    let output = Command::new("sudo")
+        .args(["-l", "-U", USERNAME])
+        .output(&env)?;
+
+    assert!(output.status().success());
+    assert_not_contains!(output.stdout()?, "comment");
+
+    Ok(())
+}
+
+#[test]
+fn blank_lines_between_rules_are_ignored() -> Result<()> {
+    let env = Env(format!(
+        "{SUDOERS_ROOT_ALL_NOPASSWD}
+
+\t
+{USERNAME} ALL=(ALL:ALL) NOPASSWD: ALL"
+    ))
+    .user(User(USERNAME).password(PASSWORD))
+    .build()?;
+
+    Command::new("sudo")
+        .arg("true")
+        .output(&env)?
+        .assert_success()?;
+
+    Command::new("sudo")
+        .args(["-S", "true"])
+        .as_user(USERNAME)
+        .output(&env)?
+        .assert_success()
+}
+
+#[test]
+fn backslash_continues_rule_on_next_line() -> Result<()> {
+    let env = Env(format!(
+        "{USERNAME} ALL=(ALL:ALL) \\
+    NOPASSWD: ALL"
+    ))
+    .user(User(USERNAME).password(PASSWORD))
+    .build()?;
+
+    // if the continuation were not honored the NOPASSWD tag would be lost or the rule rejected
+    Command::new("sudo")
+        .args(["-S", "true"])
+        .as_user(USERNAME)
+        .output(&env)?
+        .assert_success()
+}
+
+#[test]
+fn rule_that_is_commented_out_is_ignored() -> Result<()> {
+    let env = Env(format!(
+        "{SUDOERS_ROOT_ALL_NOPASSWD}
+# {USERNAME} ALL=(ALL:ALL) NOPASSWD: ALL"
+    ))
+    .user(User(USERNAME).password(PASSWORD))
+    .build()?;
+
+    let output = Command::new("sudo")
+        .arg("true")
+        .as_user(USERNAME)
+        .output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+
+    let diagnostic = if sudo_test::is_original_sudo() {
+        format!("{USERNAME} is not in the sudoers file")
+    } else {
+        format!("authentication failed: I'm sorry {USERNAME}. I'm afraid I can't do that")
+    };
+    assert_contains!(output.stderr(), diagnostic);
+
+    Ok(())
+}
+
+#[test]
+fn syntax_error_is_reported_with_location_and_command_is_not_run() -> Result<()> {
+    let marker = "/tmp/sudo-ran-the-command";
+    let env = Env("Defaults env_reset
+root ALL=(ALL:ALL) NOPASSWD ALL")
+    .build()?;
+
+    let output = Command::new("sudo").args(["touch", marker]).output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+
+    let stderr = output.stderr();
+    assert_contains!(stderr, "syntax error");
+    assert_contains!(stderr, "/etc/sudoers:2:");
+
+    Command::new("sh")
+        .args(["-c", &format!("test ! -e {marker}")])
+        .output(&env)?
+        .assert_success()
+}
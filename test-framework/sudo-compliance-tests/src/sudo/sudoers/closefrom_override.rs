@@ -0,0 +1,155 @@
+//! `Defaults closefrom_override`: permits the invoking user to use the `-C` flag
+
+use sudo_test::{Command, Env};
+
+use crate::{Result, SUDOERS_ALL_ALL_NOPASSWD};
+
+#[test]
+fn close_from_flag_is_forbidden_by_default() -> Result<()> {
+    let env = Env(SUDOERS_ALL_ALL_NOPASSWD).build()?;
+
+    let output = Command::new("sudo")
+        .args(["-C", "5", "true"])
+        .output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+    assert_contains!(
+        output.stderr(),
+        "you are not permitted to use the -C option"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn close_from_flag_is_forbidden_when_disabled() -> Result<()> {
+    let env = Env([SUDOERS_ALL_ALL_NOPASSWD, "Defaults !closefrom_override"]).build()?;
+
+    let output = Command::new("sudo")
+        .args(["-C", "5", "true"])
+        .output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+    assert_contains!(
+        output.stderr(),
+        "you are not permitted to use the -C option"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn large_close_from_value_is_rejected_without_delay() -> Result<()> {
+    let env = Env(SUDOERS_ALL_ALL_NOPASSWD).build()?;
+
+    let output = Command::new("sudo")
+        .args(["-C", "2147483647", "true"])
+        .output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+    assert_contains!(
+        output.stderr(),
+        "you are not permitted to use the -C option"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn large_close_from_value_is_accepted_when_enabled() -> Result<()> {
+    let env = Env([SUDOERS_ALL_ALL_NOPASSWD, "Defaults closefrom_override"]).build()?;
+
+    Command::new("sudo")
+        .args(["-C", "2147483647", "true"])
+        .output(&env)?
+        .assert_success()
+}
+
+#[test]
+fn close_from_flag_is_permitted_when_enabled() -> Result<()> {
+    let env = Env([SUDOERS_ALL_ALL_NOPASSWD, "Defaults closefrom_override"]).build()?;
+
+    Command::new("sudo")
+        .args(["-C", "5", "true"])
+        .output(&env)?
+        .assert_success()
+}
+
+#[test]
+fn file_descriptors_below_close_from_value_are_kept_open() -> Result<()> {
+    let env = Env([SUDOERS_ALL_ALL_NOPASSWD, "Defaults closefrom_override"]).build()?;
+
+    Command::new("bash")
+        .args([
+            "-c",
+            "exec 5>/dev/null; sudo -C 6 sh -c 'test -e /proc/$$/fd/5'",
+        ])
+        .output(&env)?
+        .assert_success()
+}
+
+#[test]
+fn file_descriptors_from_close_from_value_are_closed() -> Result<()> {
+    let env = Env([SUDOERS_ALL_ALL_NOPASSWD, "Defaults closefrom_override"]).build()?;
+
+    Command::new("bash")
+        .args([
+            "-c",
+            "exec 5>/dev/null; sudo -C 5 sh -c 'test ! -e /proc/$$/fd/5'",
+        ])
+        .output(&env)?
+        .assert_success()
+}
+
+fn assert_only_stdio_is_open(output: &str) {
+    let fds = output.lines().map(str::trim).collect::<Vec<_>>();
+    assert_eq!(["0", "1", "2"], fds.as_slice(), "{output}");
+}
+
+#[test]
+fn internal_file_descriptors_are_not_passed_on() -> Result<()> {
+    let env = Env([SUDOERS_ALL_ALL_NOPASSWD, "Defaults closefrom_override"]).build()?;
+
+    let output = Command::new("sudo")
+        .args(["-C", "100", "sh", "-c", "ls /proc/$$/fd"])
+        .output(&env)?
+        .stdout()?;
+
+    assert_only_stdio_is_open(&output);
+
+    Ok(())
+}
+
+#[test]
+fn internal_file_descriptors_are_not_passed_on_with_pty() -> Result<()> {
+    let env = Env([SUDOERS_ALL_ALL_NOPASSWD, "Defaults closefrom_override"]).build()?;
+
+    // with a terminal, sudo also has the pty and its back-channel sockets open
+    let output = Command::new("sudo")
+        .args(["-C", "100", "sh", "-c", "ls /proc/$$/fd"])
+        .tty(true)
+        .output(&env)?
+        .stdout()?;
+
+    assert_only_stdio_is_open(&output);
+
+    Ok(())
+}
+
+#[test]
+fn close_from_value_must_be_at_least_three() -> Result<()> {
+    let env = Env([SUDOERS_ALL_ALL_NOPASSWD, "Defaults closefrom_override"]).build()?;
+
+    let output = Command::new("sudo")
+        .args(["-C", "2", "true"])
+        .output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+    assert_contains!(output.stderr(), "greater than or equal to 3");
+
+    Ok(())
+}
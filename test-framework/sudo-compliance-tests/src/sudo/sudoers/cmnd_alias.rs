@@ -481,3 +481,67 @@ fn keywords() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn relative_path_is_rejected() -> Result<()> {
+    let env = Env([
+        "Cmnd_Alias CMDSGROUP = true",
+        "ALL ALL=(ALL:ALL) NOPASSWD: CMDSGROUP",
+    ])
+    .build()?;
+
+    let output = Command::new("sudo").arg("true").output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+
+    let diagnostic = if sudo_test::is_original_sudo() {
+        "syntax error"
+    } else {
+        "command 'true' is not specified using an absolute path"
+    };
+    assert_contains!(output.stderr(), diagnostic);
+
+    Ok(())
+}
+
+#[test]
+fn relative_path_in_rule_is_rejected() -> Result<()> {
+    let env = Env("ALL ALL=(ALL:ALL) NOPASSWD: true").build()?;
+
+    let output = Command::new("sudo").arg("true").output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+
+    let diagnostic = if sudo_test::is_original_sudo() {
+        "syntax error"
+    } else {
+        "command 'true' is not specified using an absolute path"
+    };
+    assert_contains!(output.stderr(), diagnostic);
+
+    Ok(())
+}
+
+#[test]
+fn relative_path_is_resolved_using_secure_path() -> Result<()> {
+    if sudo_test::is_original_sudo() {
+        // the original sudo always requires an absolute path
+        return Ok(());
+    }
+
+    let env = Env([
+        "Defaults secure_path=/usr/bin:/bin",
+        "Cmnd_Alias CMDSGROUP = true",
+        "ALL ALL=(ALL:ALL) NOPASSWD: CMDSGROUP",
+    ])
+    .build()?;
+
+    let output = Command::new("sudo").arg("true").output(&env)?;
+
+    assert!(output.status().success(), "{}", output.stderr());
+    assert!(output.stderr().is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+//! `Defaults command_timeout` and the `TIMEOUT` tag: the command is terminated once it has been
+//! running for the given number of seconds
+
+use std::time::{Duration, Instant};
+
+use sudo_test::{Command, Env};
+
+use crate::Result;
+
+fn assert_terminated_early(env: &Env) -> Result<()> {
+    let start = Instant::now();
+    let output = Command::new("sudo").args(["sleep", "10"]).output(env)?;
+    let elapsed = start.elapsed();
+
+    assert!(!output.status().success());
+    assert!(
+        elapsed < Duration::from_secs(8),
+        "command ran for {elapsed:?}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn global_default_terminates_command() -> Result<()> {
+    let env = Env([
+        "Defaults command_timeout=1",
+        "root ALL=(ALL:ALL) NOPASSWD: ALL",
+    ])
+    .build()?;
+
+    assert_terminated_early(&env)
+}
+
+#[test]
+fn global_default_does_not_affect_short_commands() -> Result<()> {
+    let env = Env([
+        "Defaults command_timeout=30",
+        "root ALL=(ALL:ALL) NOPASSWD: ALL",
+    ])
+    .build()?;
+
+    let output = Command::new("sudo").args(["echo", "hello"]).output(&env)?;
+
+    assert!(output.status().success(), "{}", output.stderr());
+    assert_eq!("hello", output.stdout()?);
+
+    Ok(())
+}
+
+#[test]
+fn tag_overrides_shorter_global_default() -> Result<()> {
+    let env = Env([
+        "Defaults command_timeout=1",
+        "root ALL=(ALL:ALL) NOPASSWD: TIMEOUT=30 ALL",
+    ])
+    .build()?;
+
+    Command::new("sudo")
+        .args(["sh", "-c", "sleep 3; echo done"])
+        .output(&env)?
+        .assert_success()
+}
+
+#[test]
+fn tag_overrides_longer_global_default() -> Result<()> {
+    let env = Env([
+        "Defaults command_timeout=30",
+        "root ALL=(ALL:ALL) NOPASSWD: TIMEOUT=1 ALL",
+    ])
+    .build()?;
+
+    assert_terminated_early(&env)
+}
+
+#[test]
+fn tag_without_global_default() -> Result<()> {
+    let env = Env("root ALL=(ALL:ALL) NOPASSWD: TIMEOUT=1 ALL").build()?;
+
+    assert_terminated_early(&env)
+}
@@ -0,0 +1,66 @@
+//! `Defaults env_file`: variables read from a file are added to the environment of the command
+
+use sudo_test::{Command, Env};
+
+use crate::{helpers, Result, SUDOERS_ALL_ALL_NOPASSWD};
+
+const ENV_FILE: &str = "/etc/sudo-environment";
+
+#[test]
+fn variables_are_added_to_the_environment() -> Result<()> {
+    let env = Env([
+        SUDOERS_ALL_ALL_NOPASSWD,
+        &format!("Defaults env_file={ENV_FILE}"),
+    ])
+    .file(
+        ENV_FILE,
+        "# site-wide settings
+FOO=bar
+
+export BAZ=\"quoted value\"",
+    )
+    .build()?;
+
+    let stdout = Command::new("sudo").arg("env").output(&env)?.stdout()?;
+    let sudo_env = helpers::parse_env_output(&stdout)?;
+
+    assert_eq!(Some(&"bar"), sudo_env.get("FOO"));
+    assert_eq!(Some(&"quoted value"), sudo_env.get("BAZ"));
+
+    Ok(())
+}
+
+#[test]
+fn does_not_override_preserved_variables() -> Result<()> {
+    let env = Env([
+        SUDOERS_ALL_ALL_NOPASSWD,
+        &format!("Defaults env_file={ENV_FILE}"),
+        "Defaults env_keep += FOO",
+    ])
+    .file(ENV_FILE, "FOO=from-file")
+    .build()?;
+
+    let stdout = Command::new("env")
+        .args(["FOO=from-user", "sudo", "env"])
+        .output(&env)?
+        .stdout()?;
+    let sudo_env = helpers::parse_env_output(&stdout)?;
+
+    assert_eq!(Some(&"from-user"), sudo_env.get("FOO"));
+
+    Ok(())
+}
+
+#[test]
+fn not_set_by_default() -> Result<()> {
+    let env = Env(SUDOERS_ALL_ALL_NOPASSWD)
+        .file(ENV_FILE, "FOO=bar")
+        .build()?;
+
+    let stdout = Command::new("sudo").arg("env").output(&env)?.stdout()?;
+    let sudo_env = helpers::parse_env_output(&stdout)?;
+
+    assert_eq!(None, sudo_env.get("FOO"));
+
+    Ok(())
+}
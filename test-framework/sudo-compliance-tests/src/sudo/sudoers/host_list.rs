@@ -121,3 +121,118 @@ fn longest_hostname() -> Result<()> {
         .output(&env)?
         .assert_success()
 }
+
+#[test]
+fn hostname_without_domain_matches_qualified_hostname() -> Result<()> {
+    let env = Env("ALL container = (ALL:ALL) ALL")
+        .hostname("container.example.com")
+        .build()?;
+
+    Command::new("sudo")
+        .arg("true")
+        .output(&env)?
+        .assert_success()
+}
+
+#[test]
+fn fully_qualified_hostname_matches() -> Result<()> {
+    let hostname = "container.example.com";
+    let env = Env(format!("ALL {hostname} = (ALL:ALL) ALL"))
+        .hostname(hostname)
+        .build()?;
+
+    Command::new("sudo")
+        .arg("true")
+        .output(&env)?
+        .assert_success()
+}
+
+#[test]
+fn fully_qualified_hostname_in_other_domain_is_rejected() -> Result<()> {
+    let env = Env("ALL container.example.org = (ALL:ALL) ALL")
+        .hostname("container.example.com")
+        .build()?;
+
+    let output = Command::new("sudo").arg("true").output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+
+    Ok(())
+}
+
+#[test]
+fn fqdn_matches_fully_qualified_hostname() -> Result<()> {
+    let hostname = "container.example.com";
+    let env = Env(["Defaults fqdn", &format!("ALL {hostname} = (ALL:ALL) ALL")])
+        .hostname(hostname)
+        .build()?;
+
+    Command::new("sudo")
+        .arg("true")
+        .output(&env)?
+        .assert_success()
+}
+
+/// makes `container.example.com` the canonical name of the `container` host
+fn add_fqdn_to_etc_hosts(env: &Env) -> Result<()> {
+    // `/etc/hosts` is bind mounted by docker so it must be rewritten in place
+    Command::new("sh")
+        .args([
+            "-c",
+            "echo '127.0.1.1 container.example.com container' | cat - /etc/hosts > /tmp/hosts \
+             && cat /tmp/hosts > /etc/hosts",
+        ])
+        .output(env)?
+        .assert_success()
+}
+
+#[test]
+fn fqdn_resolves_short_hostname() -> Result<()> {
+    let env = Env(["Defaults fqdn", "ALL container.example.com = (ALL:ALL) ALL"])
+        .hostname("container")
+        .build()?;
+    add_fqdn_to_etc_hosts(&env)?;
+
+    Command::new("sudo")
+        .arg("true")
+        .output(&env)?
+        .assert_success()
+}
+
+#[test]
+fn fully_qualified_rule_does_not_match_short_hostname_without_fqdn() -> Result<()> {
+    let env = Env("ALL container.example.com = (ALL:ALL) ALL")
+        .hostname("container")
+        .build()?;
+    add_fqdn_to_etc_hosts(&env)?;
+
+    let output = Command::new("sudo").arg("true").output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+
+    Ok(())
+}
+
+#[test]
+fn ip_network_matches_local_interface() -> Result<()> {
+    let env = Env("ALL 0.0.0.0/0 = (ALL:ALL) ALL").build()?;
+
+    Command::new("sudo")
+        .arg("true")
+        .output(&env)?
+        .assert_success()
+}
+
+#[test]
+fn loopback_address_does_not_match() -> Result<()> {
+    let env = Env("ALL 127.0.0.1 = (ALL:ALL) ALL").build()?;
+
+    let output = Command::new("sudo").arg("true").output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+//! `Defaults ignore_unknown_defaults`: do not complain about unknown Defaults options
+
+use sudo_test::{Command, Env};
+
+use crate::{Result, SUDOERS_ROOT_ALL_NOPASSWD, USERNAME};
+
+const UNKNOWN_DEFAULTS: &str = "Defaults this_option_does_not_exist, !neither_does_this_one";
+
+#[test]
+fn unknown_option_produces_warning() -> Result<()> {
+    let env = Env([UNKNOWN_DEFAULTS, SUDOERS_ROOT_ALL_NOPASSWD]).build()?;
+
+    let output = Command::new("sudo").arg("true").output(&env)?;
+
+    assert!(output.status().success(), "{}", output.stderr());
+    assert_contains!(output.stderr(), "this_option_does_not_exist");
+
+    Ok(())
+}
+
+#[test]
+fn unknown_option_is_silently_ignored() -> Result<()> {
+    let env = Env([
+        "Defaults ignore_unknown_defaults",
+        UNKNOWN_DEFAULTS,
+        SUDOERS_ROOT_ALL_NOPASSWD,
+    ])
+    .build()?;
+
+    let output = Command::new("sudo").arg("true").output(&env)?;
+
+    assert!(output.status().success(), "{}", output.stderr());
+    assert!(output.stderr().is_empty(), "{}", output.stderr());
+
+    Ok(())
+}
+
+#[test]
+fn takes_effect_regardless_of_position() -> Result<()> {
+    let env = Env([
+        UNKNOWN_DEFAULTS,
+        SUDOERS_ROOT_ALL_NOPASSWD,
+        "Defaults ignore_unknown_defaults",
+    ])
+    .build()?;
+
+    let output = Command::new("sudo").arg("true").output(&env)?;
+
+    assert!(output.status().success(), "{}", output.stderr());
+    assert!(output.stderr().is_empty(), "{}", output.stderr());
+
+    Ok(())
+}
+
+#[test]
+fn known_options_on_the_same_line_still_apply() -> Result<()> {
+    let env = Env([
+        "Defaults ignore_unknown_defaults".to_string(),
+        format!("Defaults this_option_does_not_exist, runas_default={USERNAME}"),
+        SUDOERS_ROOT_ALL_NOPASSWD.to_string(),
+    ])
+    .user(USERNAME)
+    .build()?;
+
+    let output = Command::new("sudo").arg("whoami").output(&env)?;
+
+    assert!(output.status().success(), "{}", output.stderr());
+    assert_eq!(USERNAME, output.stdout()?);
+
+    Ok(())
+}
@@ -0,0 +1,147 @@
+//! `Defaults mail_always`: a mail is sent to the administrator for every invocation
+
+use sudo_test::{Command, Env, TextFile, User};
+
+use crate::{Result, PASSWORD, SUDOERS_ALL_ALL_NOPASSWD, USERNAME};
+
+const SENDMAIL: &str = "/usr/sbin/sendmail";
+const MAIL: &str = "/tmp/mail.txt";
+
+// stores the mail instead of delivering it
+fn stub_sendmail() -> TextFile {
+    TextFile(format!("#!/bin/sh\ncat > {MAIL}")).chmod("755")
+}
+
+// the mail may be sent in the background, so give it some time to arrive
+fn read_mail(env: &Env) -> Result<String> {
+    Command::new("sh")
+        .args([
+            "-c",
+            &format!("for i in 1 2 3 4 5; do test -s {MAIL} && break; sleep 1; done; cat {MAIL}"),
+        ])
+        .output(env)?
+        .stdout()
+}
+
+#[test]
+fn mail_is_sent_for_allowed_command() -> Result<()> {
+    let env = Env([SUDOERS_ALL_ALL_NOPASSWD, "Defaults mail_always"])
+        .file(SENDMAIL, stub_sendmail())
+        .build()?;
+
+    Command::new("sudo")
+        .arg("true")
+        .output(&env)?
+        .assert_success()?;
+
+    let mail = read_mail(&env)?;
+
+    assert_contains!(mail, "To: root");
+    assert_contains!(mail, "SECURITY information");
+    assert_contains!(mail, "COMMAND=/usr/bin/true");
+
+    Ok(())
+}
+
+#[test]
+fn mail_is_sent_for_denied_command() -> Result<()> {
+    let env = Env("Defaults mail_always")
+        .file(SENDMAIL, stub_sendmail())
+        .user(USERNAME)
+        .build()?;
+
+    let output = Command::new("sudo")
+        .args(["-n", "true"])
+        .as_user(USERNAME)
+        .output(&env)?;
+
+    assert!(!output.status().success());
+
+    let mail = read_mail(&env)?;
+
+    assert_contains!(mail, "SECURITY information");
+    assert_contains!(mail, USERNAME);
+
+    Ok(())
+}
+
+#[test]
+fn mail_is_sent_on_authentication_failure() -> Result<()> {
+    let env = Env(["ALL ALL=(ALL:ALL) ALL", "Defaults mail_always"])
+        .file(SENDMAIL, stub_sendmail())
+        .user(User(USERNAME).password(PASSWORD))
+        .build()?;
+
+    let output = Command::new("sudo")
+        .args(["-S", "true"])
+        .as_user(USERNAME)
+        .stdin("incorrect-password")
+        .output(&env)?;
+
+    assert!(!output.status().success());
+
+    let mail = read_mail(&env)?;
+
+    assert_contains!(mail, "SECURITY information");
+    assert_contains!(mail, USERNAME);
+    assert_contains!(mail, "COMMAND=/usr/bin/true");
+
+    Ok(())
+}
+
+#[test]
+fn mail_is_sent_when_command_is_not_found() -> Result<()> {
+    let env = Env([SUDOERS_ALL_ALL_NOPASSWD, "Defaults mail_always"])
+        .file(SENDMAIL, stub_sendmail())
+        .build()?;
+
+    let output = Command::new("sudo").arg("/does/not/exist").output(&env)?;
+
+    assert!(!output.status().success());
+
+    let mail = read_mail(&env)?;
+
+    assert_contains!(mail, "SECURITY information");
+    assert_contains!(mail, "/does/not/exist");
+
+    Ok(())
+}
+
+#[test]
+fn mailer_does_not_inherit_the_environment() -> Result<()> {
+    let env = Env([SUDOERS_ALL_ALL_NOPASSWD, "Defaults mail_always"])
+        .file(
+            SENDMAIL,
+            TextFile(format!("#!/bin/sh\nenv > {MAIL}")).chmod("755"),
+        )
+        .build()?;
+
+    Command::new("env")
+        .args(["SUDO_TEST_SECRET=1", "sudo", "true"])
+        .output(&env)?
+        .assert_success()?;
+
+    let mailer_env = read_mail(&env)?;
+
+    assert_not_contains!(mailer_env, "SUDO_TEST_SECRET");
+    assert_contains!(mailer_env, "PATH=/usr/bin:/bin:/usr/sbin:/sbin");
+
+    Ok(())
+}
+
+#[test]
+fn no_mail_is_sent_by_default() -> Result<()> {
+    let env = Env(SUDOERS_ALL_ALL_NOPASSWD)
+        .file(SENDMAIL, stub_sendmail())
+        .build()?;
+
+    Command::new("sudo")
+        .arg("true")
+        .output(&env)?
+        .assert_success()?;
+
+    Command::new("sh")
+        .args(["-c", &format!("sleep 2; test ! -e {MAIL}")])
+        .output(&env)?
+        .assert_success()
+}
@@ -0,0 +1,78 @@
+//! `Defaults rootpw`: the invoking user must enter the password of the root user
+
+use sudo_test::{Command, Env, User};
+
+use crate::{Result, OTHER_USERNAME, PASSWORD, SUDOERS_NO_LECTURE, USERNAME};
+
+const ROOT_PASSWORD: &str = "root-password";
+
+fn env() -> Result<sudo_test::Env> {
+    let env = Env([
+        "Defaults rootpw",
+        SUDOERS_NO_LECTURE,
+        "ALL ALL=(ALL:ALL) ALL",
+    ])
+    .user(User(USERNAME).password(PASSWORD))
+    .user(OTHER_USERNAME)
+    .build()?;
+
+    Command::new("chpasswd")
+        .stdin(format!("root:{ROOT_PASSWORD}"))
+        .output(&env)?
+        .assert_success()?;
+
+    Ok(env)
+}
+
+#[test]
+fn requires_root_password() -> Result<()> {
+    let env = env()?;
+
+    let output = Command::new("sudo")
+        .args(["-S", "true"])
+        .as_user(USERNAME)
+        .stdin(ROOT_PASSWORD)
+        .output(&env)?;
+
+    assert!(output.status().success(), "{}", output.stderr());
+    if sudo_test::is_original_sudo() {
+        assert_contains!(output.stderr(), "[sudo] password for root:");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn requires_root_password_regardless_of_target_user() -> Result<()> {
+    let env = env()?;
+
+    Command::new("sudo")
+        .args(["-S", "-u", OTHER_USERNAME, "true"])
+        .as_user(USERNAME)
+        .stdin(ROOT_PASSWORD)
+        .output(&env)?
+        .assert_success()
+}
+
+#[test]
+fn rejects_password_of_invoking_user() -> Result<()> {
+    let env = env()?;
+
+    let output = Command::new("sudo")
+        .args(["-S", "true"])
+        .as_user(USERNAME)
+        .stdin(PASSWORD)
+        .output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+
+    let diagnostic = if sudo_test::is_original_sudo() {
+        "incorrect password attempt"
+    } else {
+        "incorrect authentication attempt"
+    };
+    assert_contains!(output.stderr(), diagnostic);
+
+    Ok(())
+}
@@ -2,7 +2,7 @@
 
 use std::collections::HashSet;
 
-use sudo_test::{Command, Env, User};
+use sudo_test::{Command, Env, Group, User};
 
 use crate::{Result, GROUPNAME, PAMD_SUDO_PAM_PERMIT, SUDOERS_NO_LECTURE, USERNAME};
 
@@ -210,6 +210,36 @@ fn when_specific_group_then_as_that_group_is_allowed() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn when_all_groups_then_as_any_group_is_allowed() -> Result<()> {
+    let wheel_gid = 1234;
+    // GID 10 (`uucp`) is part of the base image
+    let groups = [("wheel", wheel_gid), ("#10", 10)];
+
+    for run_as in ["ALL:ALL", &format!("{USERNAME}:ALL"), ":ALL"] {
+        let env = Env(format!("{USERNAME} ALL=({run_as}) NOPASSWD: ALL"))
+            .user(USERNAME)
+            .group(Group("wheel").id(wheel_gid))
+            .build()?;
+
+        for (group, gid) in groups {
+            let output = Command::new("sudo")
+                .args(["-g", group, "id", "-g"])
+                .as_user(USERNAME)
+                .output(&env)?;
+
+            assert!(
+                output.status().success(),
+                "run_as: ({run_as}); group: {group}; stderr: {}",
+                output.stderr()
+            );
+            assert_eq!(gid.to_string(), output.stdout()?);
+        }
+    }
+
+    Ok(())
+}
+
 #[test]
 fn when_specific_group_then_as_a_different_group_is_not_allowed() -> Result<()> {
     let env = Env([&format!("ALL ALL=(:{GROUPNAME})  ALL"), SUDOERS_NO_LECTURE])
@@ -483,3 +513,96 @@ fn minus_1_uid() -> Result<()> {
 
     Ok(())
 }
+
+fn run_as_ids(env: &Env, invoking_user: &str, args: &[&str]) -> Result<(String, String)> {
+    let uid = Command::new("sudo")
+        .args(args)
+        .args(["id", "-u"])
+        .as_user(invoking_user)
+        .output(env)?
+        .stdout()?;
+    let gid = Command::new("sudo")
+        .args(args)
+        .args(["id", "-g"])
+        .as_user(invoking_user)
+        .output(env)?
+        .stdout()?;
+
+    Ok((uid, gid))
+}
+
+#[test]
+fn numeric_uid_and_gid_of_root() -> Result<()> {
+    let env = Env(format!("{USERNAME} ALL=(#0:#0) NOPASSWD: ALL"))
+        .user(USERNAME)
+        .build()?;
+
+    for args in [&[][..], &["-u", "#0"], &["-u", "#0", "-g", "#0"]] {
+        let (uid, gid) = run_as_ids(&env, USERNAME, args)?;
+
+        assert_eq!("0", uid, "args: {args:?}");
+        assert_eq!("0", gid, "args: {args:?}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn numeric_uid_only() -> Result<()> {
+    let env = Env("root ALL=(#1000) NOPASSWD: ALL")
+        .user(User(USERNAME).id(1000))
+        .build()?;
+
+    for user in ["#1000", USERNAME] {
+        let (uid, _) = run_as_ids(&env, "root", &["-u", user])?;
+        assert_eq!("1000", uid, "user: {user}");
+    }
+
+    let output = Command::new("sudo")
+        .args(["-u", "#0", "true"])
+        .output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+
+    Ok(())
+}
+
+#[test]
+fn numeric_gid_only() -> Result<()> {
+    // GID 100 (`users`) is part of the base image
+    let env = Env("root ALL=(:#100) NOPASSWD: ALL").build()?;
+
+    let (uid, gid) = run_as_ids(&env, "root", &["-g", "#100"])?;
+    assert_eq!("0", uid);
+    assert_eq!("100", gid);
+
+    let output = Command::new("sudo")
+        .args(["-g", "#10", "true"])
+        .output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+
+    Ok(())
+}
+
+#[test]
+fn numeric_uid_and_gid() -> Result<()> {
+    let env = Env("root ALL=(#1000:#100) NOPASSWD: ALL")
+        .user(User(USERNAME).id(1000))
+        .build()?;
+
+    let (uid, gid) = run_as_ids(&env, "root", &["-u", "#1000", "-g", "#100"])?;
+    assert_eq!("1000", uid);
+    assert_eq!("100", gid);
+
+    for args in [["-u", "#1001", "-g", "#100"], ["-u", "#1000", "-g", "#10"]] {
+        let output = Command::new("sudo").args(args).arg("true").output(&env)?;
+
+        assert!(!output.status().success(), "args: {args:?}");
+        assert_eq!(Some(1), output.status().code(), "args: {args:?}");
+    }
+
+    Ok(())
+}
@@ -41,6 +41,46 @@ fn runas_alias_works() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn runas_alias_with_group_member() -> Result<()> {
+    let env = Env([
+        &format!("Runas_Alias OPS = %{GROUPNAME}, operator"),
+        &format!("{USERNAME} ALL = (OPS) NOPASSWD: ALL"),
+    ])
+    .user(USERNAME)
+    .user(User("member").secondary_group(GROUPNAME))
+    .user("operator")
+    .user("otheruser")
+    .group(GROUPNAME)
+    .build()?;
+
+    // the group member matches on the group membership of the target user
+    for target in ["member", "operator"] {
+        Command::new("sudo")
+            .args(["-u", target, "true"])
+            .as_user(USERNAME)
+            .output(&env)?
+            .assert_success()?;
+    }
+
+    let output = Command::new("sudo")
+        .args(["-u", "otheruser", "true"])
+        .as_user(USERNAME)
+        .output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+
+    let diagnostic = if sudo_test::is_original_sudo() {
+        format!("user {USERNAME} is not allowed to execute '/usr/bin/true' as otheruser")
+    } else {
+        format!("authentication failed: I'm sorry {USERNAME}. I'm afraid I can't do that")
+    };
+    assert_contains!(output.stderr(), diagnostic);
+
+    Ok(())
+}
+
 #[test]
 fn underscore() -> Result<()> {
     let env = Env([
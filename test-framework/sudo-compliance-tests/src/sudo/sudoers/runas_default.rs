@@ -0,0 +1,86 @@
+//! `Defaults runas_default`: the user that commands are run as when `sudo` is invoked without `-u`
+
+use sudo_test::{Command, Env};
+
+use crate::{Result, SUDOERS_ALL_ALL_NOPASSWD};
+
+#[test]
+fn runs_as_root_by_default() -> Result<()> {
+    let env = Env(SUDOERS_ALL_ALL_NOPASSWD).build()?;
+
+    let output = Command::new("sudo").args(["id", "-un"]).output(&env)?;
+
+    assert!(output.status().success(), "{}", output.stderr());
+    assert_eq!("root", output.stdout()?);
+
+    Ok(())
+}
+
+#[test]
+fn runs_as_configured_user() -> Result<()> {
+    let env = Env(["Defaults runas_default=operator", SUDOERS_ALL_ALL_NOPASSWD])
+        .user("operator")
+        .build()?;
+
+    let output = Command::new("sudo").args(["id", "-un"]).output(&env)?;
+
+    assert!(output.status().success(), "{}", output.stderr());
+    assert_eq!("operator", output.stdout()?);
+
+    Ok(())
+}
+
+#[test]
+fn group_is_primary_group_of_configured_user() -> Result<()> {
+    let env = Env(["Defaults runas_default=operator", SUDOERS_ALL_ALL_NOPASSWD])
+        .user("operator")
+        .build()?;
+
+    let expected = Command::new("id")
+        .args(["-gn", "operator"])
+        .output(&env)?
+        .stdout()?;
+
+    let output = Command::new("sudo").args(["id", "-gn"]).output(&env)?;
+
+    assert!(output.status().success(), "{}", output.stderr());
+    assert_eq!(expected, output.stdout()?);
+
+    Ok(())
+}
+
+#[test]
+fn rule_without_runas_spec_allows_configured_user() -> Result<()> {
+    let env = Env([
+        "Defaults runas_default=operator",
+        "root ALL=NOPASSWD: /usr/bin/id",
+    ])
+    .user("operator")
+    .build()?;
+
+    let output = Command::new("sudo").args(["id", "-un"]).output(&env)?;
+
+    assert!(output.status().success(), "{}", output.stderr());
+    assert_eq!("operator", output.stdout()?);
+
+    Ok(())
+}
+
+#[test]
+fn rule_without_runas_spec_no_longer_allows_root() -> Result<()> {
+    let env = Env([
+        "Defaults runas_default=operator",
+        "root ALL=NOPASSWD: /usr/bin/id",
+    ])
+    .user("operator")
+    .build()?;
+
+    let output = Command::new("sudo")
+        .args(["-u", "root", "id", "-un"])
+        .output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+
+    Ok(())
+}
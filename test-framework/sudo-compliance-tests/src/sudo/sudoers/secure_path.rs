@@ -1,6 +1,6 @@
 use sudo_test::{Command, Env, TextFile};
 
-use crate::{Result, SUDOERS_ALL_ALL_NOPASSWD};
+use crate::{Result, SUDOERS_ALL_ALL_NOPASSWD, USERNAME};
 
 macro_rules! assert_snapshot {
     ($($tt:tt)*) => {
@@ -111,3 +111,62 @@ ALL ALL=(ALL:ALL) NOPASSWD: ALL"
 
     Ok(())
 }
+
+#[test]
+fn command_in_usr_local_bin_is_found_and_executed() -> Result<()> {
+    let path = "/usr/local/bin/my-script";
+    let env = Env(format!(
+        "Defaults secure_path=/usr/local/bin:/usr/bin:/bin
+{USERNAME} ALL=(ALL:ALL) NOPASSWD: {path}"
+    ))
+    .user(USERNAME)
+    .file(
+        path,
+        TextFile("#!/bin/sh\necho hello from local bin").chmod("755"),
+    )
+    .build()?;
+
+    for command in ["my-script", path] {
+        let output = Command::new("sudo")
+            .arg(command)
+            .as_user(USERNAME)
+            .output(&env)?;
+
+        assert!(output.status().success(), "{}", output.stderr());
+        assert_eq!("hello from local bin", output.stdout()?);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn if_set_without_directory_command_in_that_directory_is_not_found() -> Result<()> {
+    let path = "/usr/local/bin/my-script";
+    let env = Env(format!(
+        "Defaults secure_path=/usr/bin:/bin
+{USERNAME} ALL=(ALL:ALL) NOPASSWD: {path}"
+    ))
+    .user(USERNAME)
+    .file(path, TextFile("#!/bin/sh").chmod("755"))
+    .build()?;
+
+    let output = Command::new("sh")
+        .args([
+            "-c",
+            "export PATH=/usr/local/bin:/usr/bin:/bin; sudo my-script",
+        ])
+        .as_user(USERNAME)
+        .output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+
+    let diagnostic = if sudo_test::is_original_sudo() {
+        "my-script: command not found"
+    } else {
+        "'my-script': command not found"
+    };
+    assert_contains!(output.stderr(), diagnostic);
+
+    Ok(())
+}
@@ -0,0 +1,105 @@
+//! `Defaults sudoedit_follow` and the `FOLLOW` / `NOFOLLOW` tags: whether sudoedit may edit a file
+//! through a symbolic link. A tag on the command takes precedence over the Defaults setting.
+
+use sudo_test::{Command, Env, TextFile};
+
+use crate::{Result, USERNAME};
+
+const EDITOR_PATH: &str = "/tmp/editor";
+const LINK_PATH: &str = "/etc/link.txt";
+const TARGET_PATH: &str = "/etc/target.txt";
+const EXPECTED: &str = "edited through the link";
+
+fn sudoedit_link(defaults: &str, tag: &str) -> Result<(Env, bool)> {
+    let env = Env(format!(
+        "{defaults}
+{USERNAME} ALL=(ALL:ALL) NOPASSWD: {tag} sudoedit {LINK_PATH}"
+    ))
+    .user(USERNAME)
+    .file(TARGET_PATH, "")
+    .file(
+        EDITOR_PATH,
+        TextFile(format!(
+            "#!/bin/sh
+for last; do true; done
+echo '{EXPECTED}' > \"$last\""
+        ))
+        .chmod("755"),
+    )
+    .build()?;
+
+    Command::new("ln")
+        .args(["-s", TARGET_PATH, LINK_PATH])
+        .output(&env)?
+        .assert_success()?;
+
+    let output = Command::new("env")
+        .args([
+            &format!("SUDO_EDITOR={EDITOR_PATH}"),
+            "sudo",
+            "-e",
+            LINK_PATH,
+        ])
+        .as_user(USERNAME)
+        .output(&env)?;
+
+    let contents = Command::new("cat")
+        .arg(TARGET_PATH)
+        .output(&env)?
+        .stdout()?;
+    let edited = contents == EXPECTED;
+    assert_eq!(output.status().success(), edited, "{}", output.stderr());
+    if !edited {
+        assert_contains!(
+            output.stderr(),
+            format!("{LINK_PATH}: editing symbolic links is not permitted")
+        );
+    }
+
+    Ok((env, edited))
+}
+
+#[test]
+#[ignore = "sudoedit"]
+fn symlinks_are_not_followed_by_default() -> Result<()> {
+    let (_env, edited) = sudoedit_link("", "")?;
+    assert!(!edited);
+
+    Ok(())
+}
+
+#[test]
+#[ignore = "sudoedit"]
+fn defaults_sudoedit_follow_follows_symlinks() -> Result<()> {
+    let (_env, edited) = sudoedit_link("Defaults sudoedit_follow", "")?;
+    assert!(edited);
+
+    Ok(())
+}
+
+#[test]
+#[ignore = "sudoedit"]
+fn follow_tag_follows_symlinks() -> Result<()> {
+    let (_env, edited) = sudoedit_link("", "FOLLOW:")?;
+    assert!(edited);
+
+    Ok(())
+}
+
+#[test]
+#[ignore = "sudoedit"]
+fn nofollow_tag_takes_precedence_over_defaults() -> Result<()> {
+    let (_env, edited) = sudoedit_link("Defaults sudoedit_follow", "NOFOLLOW:")?;
+    assert!(!edited);
+
+    Ok(())
+}
+
+#[test]
+#[ignore = "sudoedit"]
+fn follow_tag_takes_precedence_over_negated_defaults() -> Result<()> {
+    let (_env, edited) = sudoedit_link("Defaults !sudoedit_follow", "FOLLOW:")?;
+    assert!(edited);
+
+    Ok(())
+}
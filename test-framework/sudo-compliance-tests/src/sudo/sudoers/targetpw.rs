@@ -0,0 +1,80 @@
+//! `Defaults targetpw`: the invoking user must enter the password of the target user
+
+use sudo_test::{Command, Env, User};
+
+use crate::{Result, OTHER_USERNAME, PASSWORD, SUDOERS_NO_LECTURE, USERNAME};
+
+const OTHER_PASSWORD: &str = "other-strong-password";
+
+fn env() -> Result<sudo_test::Env> {
+    let env = Env([
+        "Defaults targetpw",
+        SUDOERS_NO_LECTURE,
+        "ALL ALL=(ALL:ALL) ALL",
+    ])
+    .user(User(USERNAME).password(PASSWORD))
+    .user(User(OTHER_USERNAME).password(OTHER_PASSWORD))
+    .build()?;
+
+    Ok(env)
+}
+
+#[test]
+#[ignore = "targetpw"]
+fn requires_password_of_target_user() -> Result<()> {
+    let env = env()?;
+
+    let output = Command::new("sudo")
+        .args(["-S", "-u", OTHER_USERNAME, "true"])
+        .as_user(USERNAME)
+        .stdin(OTHER_PASSWORD)
+        .output(&env)?;
+
+    assert!(output.status().success(), "{}", output.stderr());
+    assert_contains!(
+        output.stderr(),
+        format!("[sudo] password for {OTHER_USERNAME}:")
+    );
+
+    Ok(())
+}
+
+#[test]
+#[ignore = "targetpw"]
+fn rejects_password_of_invoking_user() -> Result<()> {
+    let env = env()?;
+
+    let output = Command::new("sudo")
+        .args(["-S", "-u", OTHER_USERNAME, "true"])
+        .as_user(USERNAME)
+        .stdin(PASSWORD)
+        .output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+    assert_contains!(output.stderr(), "incorrect password attempt");
+
+    Ok(())
+}
+
+#[test]
+#[ignore = "targetpw"]
+fn requires_root_password_if_no_target_user_is_given() -> Result<()> {
+    let root_password = "root-password";
+    let env = env()?;
+    Command::new("chpasswd")
+        .stdin(format!("root:{root_password}"))
+        .output(&env)?
+        .assert_success()?;
+
+    let output = Command::new("sudo")
+        .args(["-S", "true"])
+        .as_user(USERNAME)
+        .stdin(root_password)
+        .output(&env)?;
+
+    assert!(output.status().success(), "{}", output.stderr());
+    assert_contains!(output.stderr(), "[sudo] password for root:");
+
+    Ok(())
+}
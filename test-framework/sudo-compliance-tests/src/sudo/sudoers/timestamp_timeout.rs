@@ -1,4 +1,4 @@
-use sudo_test::{Command, Env, User};
+use sudo_test::{Command, Env, TextFile, User};
 
 use crate::{Result, PASSWORD, USERNAME};
 
@@ -85,3 +85,56 @@ Defaults timestamp_timeout=0"
 
     Ok(())
 }
+
+// counts the number of times the PAM authentication stack is invoked
+const COUNT_AUTH: &str = "/usr/local/bin/count-auth";
+const AUTH_COUNT: &str = "/tmp/auth-count";
+
+fn count_auth_env(sudoers: &str) -> Result<Env> {
+    Env([&format!("{USERNAME} ALL=(ALL:ALL) ALL"), sudoers])
+        .user(USERNAME)
+        .file(
+            "/etc/pam.d/sudo",
+            format!("auth required pam_exec.so {COUNT_AUTH}\nauth sufficient pam_permit.so"),
+        )
+        .file(
+            COUNT_AUTH,
+            TextFile(format!("#!/bin/sh\necho $PAM_TYPE >> {AUTH_COUNT}")).chmod("755"),
+        )
+        .build()
+}
+
+fn auth_count_after_two_invocations(env: &Env) -> Result<usize> {
+    Command::new("sh")
+        .args(["-c", "sudo true && sudo true"])
+        .as_user(USERNAME)
+        .output(env)?
+        .assert_success()?;
+
+    let count = Command::new("cat")
+        .arg(AUTH_COUNT)
+        .output(env)?
+        .stdout()?
+        .lines()
+        .count();
+
+    Ok(count)
+}
+
+#[test]
+fn zero_authenticates_every_invocation() -> Result<()> {
+    let env = count_auth_env("Defaults timestamp_timeout=0")?;
+
+    assert_eq!(2, auth_count_after_two_invocations(&env)?);
+
+    Ok(())
+}
+
+#[test]
+fn nonzero_authenticates_once() -> Result<()> {
+    let env = count_auth_env("Defaults timestamp_timeout=5")?;
+
+    assert_eq!(1, auth_count_after_two_invocations(&env)?);
+
+    Ok(())
+}
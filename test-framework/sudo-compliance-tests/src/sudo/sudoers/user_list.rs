@@ -1,9 +1,11 @@
 //! Test the first component of the user specification: `<user_list> ALL=(ALL:ALL) ALL`
 
 use pretty_assertions::assert_eq;
-use sudo_test::{Command, Env, User};
+use sudo_test::{Command, Env, Group, User};
 
-use crate::{Result, PAMD_SUDO_PAM_PERMIT, SUDOERS_NO_LECTURE, USERNAME};
+use crate::{
+    Result, PAMD_SUDO_PAM_PERMIT, PASSWORD, SUDOERS_NO_LECTURE, SUDOERS_ROOT_ALL_NOPASSWD, USERNAME,
+};
 
 macro_rules! assert_snapshot {
     ($($tt:tt)*) => {
@@ -74,6 +76,122 @@ fn user_id() -> Result<()> {
         .assert_success()
 }
 
+#[test]
+fn user_id_zero_only_matches_root() -> Result<()> {
+    let env = Env("#0 ALL=(ALL:ALL) NOPASSWD: ALL")
+        .user(USERNAME)
+        .build()?;
+
+    Command::new("sudo")
+        .arg("true")
+        .output(&env)?
+        .assert_success()?;
+
+    let output = Command::new("sudo")
+        .arg("true")
+        .as_user(USERNAME)
+        .output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+
+    let diagnostic = if sudo_test::is_original_sudo() {
+        format!("{USERNAME} is not in the sudoers file")
+    } else {
+        format!("authentication failed: I'm sorry {USERNAME}. I'm afraid I can't do that")
+    };
+    assert_contains!(output.stderr(), diagnostic);
+
+    Ok(())
+}
+
+#[test]
+fn user_id_of_other_user_does_not_match_root() -> Result<()> {
+    let uid = 1000;
+    let env = Env(format!("#{uid} ALL=(ALL:ALL) NOPASSWD: ALL"))
+        .user(User(USERNAME).id(uid))
+        .build()?;
+
+    Command::new("sudo")
+        .arg("true")
+        .as_user(USERNAME)
+        .output(&env)?
+        .assert_success()?;
+
+    let output = Command::new("sudo").arg("true").output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+
+    let diagnostic = if sudo_test::is_original_sudo() {
+        "root is not in the sudoers file"
+    } else {
+        "authentication failed: I'm sorry root. I'm afraid I can't do that"
+    };
+    assert_contains!(output.stderr(), diagnostic);
+
+    Ok(())
+}
+
+#[test]
+fn user_id_above_16_bits() -> Result<()> {
+    for uid in [65536, 100000] {
+        let env = Env(format!("#{uid} ALL=(ALL:ALL) NOPASSWD: ALL"))
+            .user(User(USERNAME).id(uid))
+            .user("ghost")
+            .build()?;
+
+        Command::new("sudo")
+            .arg("true")
+            .as_user(USERNAME)
+            .output(&env)?
+            .assert_success()?;
+
+        let output = Command::new("sudo")
+            .arg("true")
+            .as_user("ghost")
+            .output(&env)?;
+
+        assert!(!output.status().success());
+        assert_eq!(Some(1), output.status().code());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn user_id_that_does_not_exist() -> Result<()> {
+    let env = Env([
+        "#4294967294 ALL=(ALL:ALL) NOPASSWD: ALL",
+        SUDOERS_ROOT_ALL_NOPASSWD,
+    ])
+    .user(USERNAME)
+    .build()?;
+
+    // the rule is syntactically valid so the rest of the file still applies
+    Command::new("sudo")
+        .arg("true")
+        .output(&env)?
+        .assert_success()?;
+
+    let output = Command::new("sudo")
+        .arg("true")
+        .as_user(USERNAME)
+        .output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+
+    let diagnostic = if sudo_test::is_original_sudo() {
+        format!("{USERNAME} is not in the sudoers file")
+    } else {
+        format!("authentication failed: I'm sorry {USERNAME}. I'm afraid I can't do that")
+    };
+    assert_contains!(output.stderr(), diagnostic);
+
+    Ok(())
+}
+
 #[test]
 fn group_name() -> Result<()> {
     let env = Env("%root ALL=(ALL:ALL) NOPASSWD: ALL").build()?;
@@ -94,6 +212,96 @@ fn group_id() -> Result<()> {
         .assert_success()
 }
 
+#[test]
+fn group_id_zero_matches_secondary_group_member() -> Result<()> {
+    let env = Env("%#0 ALL=(ALL:ALL) NOPASSWD: ALL")
+        .user(User(USERNAME).secondary_group("root"))
+        .user("ghost")
+        .build()?;
+
+    Command::new("sudo")
+        .arg("true")
+        .as_user(USERNAME)
+        .output(&env)?
+        .assert_success()?;
+
+    let output = Command::new("sudo")
+        .arg("true")
+        .as_user("ghost")
+        .output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+
+    let diagnostic = if sudo_test::is_original_sudo() {
+        "ghost is not in the sudoers file"
+    } else {
+        "authentication failed: I'm sorry ghost. I'm afraid I can't do that"
+    };
+    assert_contains!(output.stderr(), diagnostic);
+
+    Ok(())
+}
+
+#[test]
+fn group_id_that_does_not_exist() -> Result<()> {
+    let env = Env("%#9999999 ALL=(ALL:ALL) NOPASSWD: ALL")
+        .user(USERNAME)
+        .build()?;
+
+    for user in ["root", USERNAME] {
+        let output = Command::new("sudo")
+            .arg("true")
+            .as_user(user)
+            .output(&env)?;
+
+        assert!(!output.status().success());
+        assert_eq!(Some(1), output.status().code());
+
+        let diagnostic = if sudo_test::is_original_sudo() {
+            format!("{user} is not in the sudoers file")
+        } else {
+            format!("authentication failed: I'm sorry {user}. I'm afraid I can't do that")
+        };
+        assert_contains!(output.stderr(), diagnostic);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn group_id_of_group_user_is_not_a_member_of() -> Result<()> {
+    let gid = 1234;
+    let env = Env(format!("%#{gid} ALL=(ALL:ALL) NOPASSWD: ALL"))
+        .user(USERNAME)
+        .user(User("ghost").secondary_group("rustaceans"))
+        .group(Group("rustaceans").id(gid))
+        .build()?;
+
+    Command::new("sudo")
+        .arg("true")
+        .as_user("ghost")
+        .output(&env)?
+        .assert_success()?;
+
+    let output = Command::new("sudo")
+        .arg("true")
+        .as_user(USERNAME)
+        .output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+
+    let diagnostic = if sudo_test::is_original_sudo() {
+        format!("{USERNAME} is not in the sudoers file")
+    } else {
+        format!("authentication failed: I'm sorry {USERNAME}. I'm afraid I can't do that")
+    };
+    assert_contains!(output.stderr(), diagnostic);
+
+    Ok(())
+}
+
 #[test]
 fn many_different() -> Result<()> {
     let env = Env(format!("root, {USERNAME} ALL=(ALL:ALL) NOPASSWD: ALL"))
@@ -112,6 +320,144 @@ fn many_different() -> Result<()> {
         .assert_success()
 }
 
+#[test]
+fn many_different_three_users() -> Result<()> {
+    let users = ["ferris", "ghost", "crab"];
+    let env = Env("root, ferris, ghost ALL=(ALL:ALL) NOPASSWD: ALL")
+        .user(users[0])
+        .user(users[1])
+        .user(users[2])
+        .build()?;
+
+    Command::new("sudo")
+        .arg("true")
+        .output(&env)?
+        .assert_success()?;
+
+    for user in &users[..2] {
+        Command::new("sudo")
+            .arg("true")
+            .as_user(user)
+            .output(&env)?
+            .assert_success()?;
+    }
+
+    let output = Command::new("sudo")
+        .arg("true")
+        .as_user(users[2])
+        .output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+
+    Ok(())
+}
+
+#[test]
+fn many_different_five_users() -> Result<()> {
+    let users = ["user1", "user2", "user3", "user4", "user5"];
+    let env = Env(format!("{} ALL=(ALL:ALL) NOPASSWD: ALL", users.join(", ")))
+        .user(users[0])
+        .user(users[1])
+        .user(users[2])
+        .user(users[3])
+        .user(users[4])
+        .user("ghost")
+        .build()?;
+
+    for user in users {
+        Command::new("sudo")
+            .arg("true")
+            .as_user(user)
+            .output(&env)?
+            .assert_success()?;
+    }
+
+    let output = Command::new("sudo")
+        .arg("true")
+        .as_user("ghost")
+        .output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+
+    Ok(())
+}
+
+#[test]
+fn many_different_with_group_and_users() -> Result<()> {
+    let env = Env(format!(
+        "root, %wheel, {USERNAME} ALL=(ALL:ALL) NOPASSWD: ALL"
+    ))
+    .user(USERNAME)
+    .user(User("ghost").secondary_group("wheel"))
+    .user("crab")
+    .group("wheel")
+    .build()?;
+
+    Command::new("sudo")
+        .arg("true")
+        .output(&env)?
+        .assert_success()?;
+
+    for user in [USERNAME, "ghost"] {
+        Command::new("sudo")
+            .arg("true")
+            .as_user(user)
+            .output(&env)?
+            .assert_success()?;
+    }
+
+    let output = Command::new("sudo")
+        .arg("true")
+        .as_user("crab")
+        .output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+
+    Ok(())
+}
+
+#[test]
+fn many_different_with_line_continuation() -> Result<()> {
+    let one_line = "root, ferris, ghost ALL=(ALL:ALL) NOPASSWD: ALL";
+    let continued = "root, \\\n    ferris, \\\n    ghost ALL=(ALL:ALL) NOPASSWD: ALL";
+
+    for sudoers in [one_line, continued] {
+        let env = Env(sudoers)
+            .user("ferris")
+            .user("ghost")
+            .user("crab")
+            .build()?;
+
+        for user in ["root", "ferris", "ghost"] {
+            Command::new("sudo")
+                .arg("true")
+                .as_user(user)
+                .output(&env)?
+                .assert_success()?;
+        }
+
+        let output = Command::new("sudo")
+            .arg("true")
+            .as_user("crab")
+            .output(&env)?;
+
+        assert!(!output.status().success());
+        assert_eq!(Some(1), output.status().code());
+
+        let output = Command::new("sudo")
+            .args(["-l", "-U", "ghost"])
+            .output(&env)?;
+
+        assert!(output.status().success());
+        assert_contains!(output.stdout()?, "(ALL : ALL) NOPASSWD: ALL");
+    }
+
+    Ok(())
+}
+
 #[test]
 fn many_repeated() -> Result<()> {
     let env = Env("root, root ALL=(ALL:ALL) NOPASSWD: ALL").build()?;
@@ -193,6 +539,97 @@ fn negation_is_order_sensitive() -> Result<()> {
         .assert_success()
 }
 
+#[test]
+fn negation_only_matches_nobody() -> Result<()> {
+    let env = Env("!root, !ferris ALL=(ALL:ALL) NOPASSWD: ALL")
+        .user("ferris")
+        .user("ghost")
+        .build()?;
+
+    for user in ["root", "ferris", "ghost"] {
+        let output = Command::new("sudo")
+            .arg("true")
+            .as_user(user)
+            .output(&env)?;
+
+        assert!(!output.status().success(), "{user} was not rejected");
+        assert_eq!(Some(1), output.status().code());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn negation_is_order_sensitive_with_user_alias() -> Result<()> {
+    // the alias behaves just like the user name it stands for
+    for (user_list, ghost_is_allowed) in [("%users, !GHOST", false), ("!GHOST, %users", true)] {
+        let env = Env(format!(
+            "User_Alias GHOST = ghost
+{user_list} ALL=(ALL:ALL) NOPASSWD: ALL"
+        ))
+        // the primary group of all new users is `users`
+        .user("ferris")
+        .user("ghost")
+        .build()?;
+
+        Command::new("sudo")
+            .arg("true")
+            .as_user("ferris")
+            .output(&env)?
+            .assert_success()?;
+
+        let output = Command::new("sudo")
+            .arg("true")
+            .as_user("ghost")
+            .output(&env)?;
+
+        assert_eq!(
+            ghost_is_allowed,
+            output.status().success(),
+            "user list: {user_list}"
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn negation_is_order_sensitive_in_all_permutations() -> Result<()> {
+    // the last item in the list that matches the user decides: a negation only excludes a user
+    // if it comes after `%users`
+    let cases = [
+        ("%users, !ferris, !ghost", false, false),
+        ("%users, !ghost, !ferris", false, false),
+        ("!ferris, %users, !ghost", true, false),
+        ("!ghost, %users, !ferris", false, true),
+        ("!ferris, !ghost, %users", true, true),
+        ("!ghost, !ferris, %users", true, true),
+    ];
+
+    for (user_list, ferris_is_allowed, ghost_is_allowed) in cases {
+        let env = Env(format!("{user_list} ALL=(ALL:ALL) NOPASSWD: ALL"))
+            // the primary group of all new users is `users`
+            .user("ferris")
+            .user("ghost")
+            .build()?;
+
+        for (user, is_allowed) in [("ferris", ferris_is_allowed), ("ghost", ghost_is_allowed)] {
+            let output = Command::new("sudo")
+                .arg("true")
+                .as_user(user)
+                .output(&env)?;
+
+            assert_eq!(
+                is_allowed,
+                output.status().success(),
+                "user list: {user_list}; user: {user}"
+            );
+        }
+    }
+
+    Ok(())
+}
+
 #[test]
 fn user_alias_works() -> Result<()> {
     let env = Env([
@@ -234,6 +671,109 @@ fn user_alias_works() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn user_alias_works_with_nopasswd() -> Result<()> {
+    let env = Env([
+        "User_Alias ADMINS = %users, !ghost",
+        "ADMINS ALL=(ALL:ALL) NOPASSWD: ALL",
+    ])
+    // the primary group of all new users is `users`
+    .user("ferris")
+    .user("ghost")
+    .build()?;
+
+    Command::new("sudo")
+        .arg("true")
+        .as_user("ferris")
+        .output(&env)?
+        .assert_success()?;
+
+    let output = Command::new("sudo")
+        .arg("true")
+        .as_user("ghost")
+        .output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+
+    let diagnostic = if sudo_test::is_original_sudo() {
+        "ghost is not in the sudoers file"
+    } else {
+        "authentication failed: I'm sorry ghost. I'm afraid I can't do that"
+    };
+    assert_contains!(output.stderr(), diagnostic);
+
+    Ok(())
+}
+
+#[test]
+fn user_alias_without_nopasswd_requires_password() -> Result<()> {
+    let env = Env([
+        "User_Alias ADMINS = %users, !ghost",
+        "ADMINS ALL=(ALL:ALL) ALL",
+        SUDOERS_NO_LECTURE,
+    ])
+    // the primary group of all new users is `users`
+    .user(User("ferris").password(PASSWORD))
+    .build()?;
+
+    let output = Command::new("sudo")
+        .args(["-S", "true"])
+        .as_user("ferris")
+        .output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+
+    let diagnostic = if sudo_test::is_original_sudo() {
+        "no password was provided"
+    } else {
+        "incorrect authentication attempt"
+    };
+    assert_contains!(output.stderr(), diagnostic);
+
+    Command::new("sudo")
+        .args(["-S", "true"])
+        .as_user("ferris")
+        .stdin(PASSWORD)
+        .output(&env)?
+        .assert_success()
+}
+
+#[test]
+fn nopasswd_on_user_alias_does_not_leak_to_other_rules() -> Result<()> {
+    let env = Env([
+        "User_Alias ADMINS = ghost",
+        "ADMINS ALL=(ALL:ALL) NOPASSWD: ALL",
+        "ferris ALL=(ALL:ALL) ALL",
+        SUDOERS_NO_LECTURE,
+    ])
+    .user(User("ferris").password(PASSWORD))
+    .user("ghost")
+    .build()?;
+
+    Command::new("sudo")
+        .arg("true")
+        .as_user("ghost")
+        .output(&env)?
+        .assert_success()?;
+
+    let output = Command::new("sudo")
+        .args(["-S", "true"])
+        .as_user("ferris")
+        .output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+
+    Command::new("sudo")
+        .args(["-S", "true"])
+        .as_user("ferris")
+        .stdin(PASSWORD)
+        .output(&env)?
+        .assert_success()
+}
+
 #[test]
 fn user_alias_can_contain_underscore_and_digits() -> Result<()> {
     let env = Env([
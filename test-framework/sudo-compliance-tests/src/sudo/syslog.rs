@@ -46,3 +46,134 @@ fn sudo_logs_every_failed_authentication_attempt() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn syslog_maxlen_truncates_long_commands() -> Result<()> {
+    if sudo_test::is_original_sudo() {
+        // original sudo splits long log entries over several messages instead
+        return Ok(());
+    }
+
+    let maxlen = 100;
+    let env = Env([
+        SUDOERS_ALL_ALL_NOPASSWD,
+        &format!("Defaults syslog_maxlen={maxlen}"),
+    ])
+    .build()?;
+    let rsyslog = Rsyslogd::start(&env)?;
+
+    let argument = "a".repeat(1000);
+    Command::new("sudo")
+        .args(["echo", &argument])
+        .output(&env)?
+        .assert_success()?;
+
+    let auth_log = rsyslog.auth_log()?;
+    let command = "/usr/bin/echo ";
+    let expected = format!("COMMAND={command}{}...", "a".repeat(maxlen - command.len()));
+    assert_contains!(auth_log, expected);
+    assert_not_contains!(auth_log, "a".repeat(maxlen));
+
+    Ok(())
+}
+
+#[test]
+fn log_denied_without_log_allowed_only_logs_denied_invocations() -> Result<()> {
+    let env = Env([
+        "Defaults !log_allowed",
+        "Defaults log_denied",
+        &format!("{USERNAME} ALL=(ALL:ALL) NOPASSWD: /usr/bin/true"),
+    ])
+    .user(USERNAME)
+    .build()?;
+    let rsyslog = Rsyslogd::start(&env)?;
+
+    Command::new("sudo")
+        .arg("true")
+        .as_user(USERNAME)
+        .output(&env)?
+        .assert_success()?;
+
+    let output = Command::new("sudo")
+        .arg("ls")
+        .as_user(USERNAME)
+        .output(&env)?;
+
+    assert!(!output.status().success());
+
+    let auth_log = rsyslog.auth_log()?;
+    assert_contains!(auth_log, "command not allowed");
+    assert_contains!(auth_log, "COMMAND=/usr/bin/ls");
+    assert_not_contains!(auth_log, "COMMAND=/usr/bin/true");
+
+    Ok(())
+}
+
+#[test]
+fn denied_invocations_are_not_logged_without_log_denied() -> Result<()> {
+    let env = Env([
+        "Defaults !log_denied",
+        &format!("{USERNAME} ALL=(ALL:ALL) NOPASSWD: /usr/bin/true"),
+    ])
+    .user(USERNAME)
+    .build()?;
+    let rsyslog = Rsyslogd::start(&env)?;
+
+    Command::new("sudo")
+        .arg("true")
+        .as_user(USERNAME)
+        .output(&env)?
+        .assert_success()?;
+
+    let output = Command::new("sudo")
+        .arg("ls")
+        .as_user(USERNAME)
+        .output(&env)?;
+
+    assert!(!output.status().success());
+
+    let auth_log = rsyslog.auth_log()?;
+    assert_contains!(auth_log, "COMMAND=/usr/bin/true");
+    assert_not_contains!(auth_log, "COMMAND=/usr/bin/ls");
+
+    Ok(())
+}
+
+#[test]
+fn syslog_pid_includes_pid_of_sudo() -> Result<()> {
+    let env = Env([
+        SUDOERS_ALL_ALL_NOPASSWD,
+        "Defaults syslog_pid",
+        // without a pty the command is a direct child of the sudo process
+        "Defaults !use_pty",
+    ])
+    .build()?;
+    let rsyslog = Rsyslogd::start(&env)?;
+
+    let sudo_pid = Command::new("sudo")
+        .args(["sh", "-c", "echo $PPID"])
+        .output(&env)?
+        .stdout()?;
+
+    let auth_log = rsyslog.auth_log()?;
+    assert_contains!(auth_log, format!("sudo[{sudo_pid}]:"));
+
+    Ok(())
+}
+
+#[test]
+fn pid_is_not_logged_by_default() -> Result<()> {
+    let env = Env(SUDOERS_ALL_ALL_NOPASSWD).build()?;
+    let rsyslog = Rsyslogd::start(&env)?;
+
+    Command::new("sudo")
+        .arg("true")
+        .output(&env)?
+        .assert_success()?;
+
+    let auth_log = rsyslog.auth_log()?;
+    assert_contains!(auth_log, "COMMAND=/usr/bin/true");
+    assert_not_contains!(auth_log, "sudo[");
+
+    Ok(())
+}
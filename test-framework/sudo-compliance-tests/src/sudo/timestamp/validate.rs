@@ -47,3 +47,90 @@ fn prompts_for_password() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn succeeds_without_running_a_command() -> Result<()> {
+    let env = Env(format!("{USERNAME} ALL=(ALL:ALL) ALL"))
+        .user(User(USERNAME).password(PASSWORD))
+        .build()?;
+
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg(format!("echo {PASSWORD} | sudo -S -v"))
+        .as_user(USERNAME)
+        .output(&env)?;
+
+    assert!(output.status().success(), "{}", output.stderr());
+    assert!(output.stdout()?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn updates_valid_timestamp_without_password() -> Result<()> {
+    let env = Env(format!("{USERNAME} ALL=(ALL:ALL) ALL"))
+        .user(User(USERNAME).password(PASSWORD))
+        .build()?;
+
+    let timestamp_file = if sudo_test::is_original_sudo() {
+        format!("/var/run/sudo/ts/{USERNAME}")
+    } else {
+        format!("/var/run/sudo-rs/ts/{USERNAME}")
+    };
+    let mtime = || -> Result<u64> {
+        Ok(Command::new("stat")
+            .args(["-c", "%Y", &timestamp_file])
+            .output(&env)?
+            .stdout()?
+            .parse()?)
+    };
+
+    Command::new("sh")
+        .arg("-c")
+        .arg(format!("echo {PASSWORD} | sudo -S true"))
+        .as_user(USERNAME)
+        .output(&env)?
+        .assert_success()?;
+
+    let before = mtime()?;
+
+    // no password is provided, the cached credentials are used
+    Command::new("sh")
+        .arg("-c")
+        .arg("sleep 2; sudo -v")
+        .as_user(USERNAME)
+        .output(&env)?
+        .assert_success()?;
+
+    let after = mtime()?;
+
+    assert!(after > before, "before: {before}; after: {after}");
+
+    Ok(())
+}
+
+#[test]
+fn expired_timestamp_requires_password() -> Result<()> {
+    let env = Env(format!(
+        "{USERNAME} ALL=(ALL:ALL) ALL
+Defaults timestamp_timeout=0.05"
+    ))
+    .user(User(USERNAME).password(PASSWORD))
+    .build()?;
+
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg(format!("echo {PASSWORD} | sudo -S true; sleep 5; sudo -v"))
+        .as_user(USERNAME)
+        .output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+
+    Command::new("sh")
+        .arg("-c")
+        .arg(format!("echo {PASSWORD} | sudo -S -v"))
+        .as_user(USERNAME)
+        .output(&env)?
+        .assert_success()
+}
@@ -1,4 +1,7 @@
-use std::{thread, time::Duration};
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
 
 use sudo_test::{Command, Env, TextFile};
 
@@ -116,6 +119,44 @@ sleep 3",
     Ok(())
 }
 
+#[test]
+fn errors_if_file_is_locked_by_another_process() -> Result<()> {
+    let lock_duration = Duration::from_secs(5);
+    let env = Env("")
+        .file(DEFAULT_EDITOR, TextFile(EDITOR_TRUE).chmod(CHMOD_EXEC))
+        .build()?;
+
+    let child = Command::new("flock")
+        .args([
+            "--exclusive",
+            ETC_SUDOERS,
+            "sleep",
+            &lock_duration.as_secs().to_string(),
+        ])
+        .spawn(&env)?;
+
+    // wait until `child` has acquired the lock
+    thread::sleep(Duration::from_secs(1));
+
+    let start = Instant::now();
+    let output = Command::new("visudo").output(&env)?;
+    let elapsed = start.elapsed();
+
+    child.wait()?.assert_success()?;
+
+    // visudo must not block until the lock is released
+    assert!(elapsed < lock_duration, "visudo took {elapsed:?}");
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+    assert_contains!(
+        output.stderr(),
+        "visudo: /etc/sudoers busy, try again later"
+    );
+
+    Ok(())
+}
+
 #[test]
 fn passes_temporary_file_to_editor() -> Result<()> {
     let env = Env("")
@@ -227,6 +268,33 @@ fn stderr_message_when_file_is_not_modified() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn does_not_touch_the_file_if_it_is_not_modified() -> Result<()> {
+    let env = Env(SUDOERS_ALL_ALL_NOPASSWD)
+        .file(DEFAULT_EDITOR, TextFile(EDITOR_TRUE).chmod(CHMOD_EXEC))
+        .build()?;
+
+    let stat_mtime = || {
+        Command::new("stat")
+            .args(["-c", "%.9Y", ETC_SUDOERS])
+            .output(&env)?
+            .stdout()
+    };
+
+    let mtime_before = stat_mtime()?;
+
+    // make sure a write would result in a different timestamp
+    thread::sleep(Duration::from_millis(10));
+
+    Command::new("visudo").output(&env)?.assert_success()?;
+
+    let mtime_after = stat_mtime()?;
+
+    assert_eq!(mtime_before, mtime_after);
+
+    Ok(())
+}
+
 #[test]
 fn does_not_save_the_file_if_there_are_syntax_errors() -> Result<()> {
     let expected = SUDOERS_ALL_ALL_NOPASSWD;
@@ -35,6 +35,22 @@ fn bad_perms() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn bad_perms_owner_writable() -> Result<()> {
+    let env = Env(TextFile("").chmod("640")).build()?;
+
+    let output = Command::new("visudo").arg("-c").output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+    assert_contains!(
+        output.stderr(),
+        "/etc/sudoers: bad permissions, should be mode 0440"
+    );
+
+    Ok(())
+}
+
 #[test]
 fn bad_ownership() -> Result<()> {
     let env = Env(TextFile("").chown(USERNAME).chmod(DEFAULT_CHMOD))
@@ -224,3 +240,135 @@ fn stdin_bad_syntax() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn relative_command_path() -> Result<()> {
+    let env = Env(
+        TextFile("Cmnd_Alias CMDSGROUP = true\nALL ALL=(ALL:ALL) CMDSGROUP").chmod(DEFAULT_CHMOD),
+    )
+    .build()?;
+
+    let output = Command::new("visudo").arg("-c").output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+    assert_contains!(output.stderr(), "syntax error");
+
+    Ok(())
+}
+
+#[test]
+fn absolute_command_path() -> Result<()> {
+    let env = Env(
+        TextFile("Cmnd_Alias CMDSGROUP = /usr/bin/true\nALL ALL=(ALL:ALL) CMDSGROUP")
+            .chmod(DEFAULT_CHMOD),
+    )
+    .build()?;
+
+    let output = Command::new("visudo").arg("-c").output(&env)?;
+
+    assert!(output.status().success(), "{}", output.stderr());
+    assert_eq!("/etc/sudoers: parsed OK", output.stdout()?);
+
+    Ok(())
+}
+
+#[test]
+fn includedir_skips_files_with_disallowed_names() -> Result<()> {
+    let env = Env(TextFile("@includedir /etc/sudoers.d").chmod(DEFAULT_CHMOD))
+        // none of these files are valid, so parsing any of them results in an error
+        .file("/etc/sudoers.d/sudoers.dpkg-dist", "this is fine")
+        .file("/etc/sudoers.d/01_base.swp", "this is fine")
+        .file("/etc/sudoers.d/.hidden", "this is fine")
+        .file("/etc/sudoers.d/03_backup~", "this is fine")
+        .file("/etc/sudoers.d/02_valid", SUDOERS_ALL_ALL_NOPASSWD)
+        .build()?;
+
+    let output = Command::new("visudo").arg("-c").output(&env)?;
+
+    assert!(output.status().success(), "{}", output.stderr());
+    assert!(output.stderr().is_empty());
+
+    let stdout = output.stdout()?;
+    assert_contains!(stdout, "/etc/sudoers: parsed OK");
+    if sudo_test::is_original_sudo() {
+        // TODO: sudo-rs does not report the included files it has checked yet
+        assert_contains!(stdout, "/etc/sudoers.d/02_valid: parsed OK");
+    }
+    for ignored in ["dpkg-dist", "01_base", ".hidden", "03_backup"] {
+        assert_not_contains!(stdout, ignored);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn includedir_checks_files_with_allowed_names() -> Result<()> {
+    let env = Env(TextFile("@includedir /etc/sudoers.d").chmod(DEFAULT_CHMOD))
+        .file("/etc/sudoers.d/02_valid", "this is fine")
+        .build()?;
+
+    let output = Command::new("visudo").arg("-c").output(&env)?;
+
+    assert!(!output.status().success());
+    assert_eq!(Some(1), output.status().code());
+    assert_contains!(output.stderr(), "syntax error");
+
+    Ok(())
+}
+
+#[test]
+fn all_supported_defaults_in_one_file() -> Result<()> {
+    // `bell` is specific to sudo-rs
+    let bell = if sudo_test::is_original_sudo() {
+        ""
+    } else {
+        "Defaults bell\n"
+    };
+    let sudoers = format!(
+        "\
+{bell}Defaults always_query_group_plugin
+Defaults always_set_home
+Defaults env_reset
+Defaults mail_badpass
+Defaults mail_always
+Defaults !match_group_by_gid
+Defaults use_pty
+Defaults !visiblepw
+Defaults env_editor
+Defaults fqdn
+Defaults rootpw
+Defaults !log_allowed
+Defaults log_denied
+Defaults sudoedit_follow
+Defaults closefrom_override
+Defaults syslog_pid
+Defaults ignore_unknown_defaults
+Defaults passwd_tries = 5
+Defaults syslog_maxlen = 500
+Defaults command_timeout = 600
+Defaults secure_path = \"/usr/local/sbin:/usr/local/bin:/usr/sbin:/usr/bin:/sbin:/bin\"
+Defaults editor = \"/usr/bin/vim:/usr/bin/vi\"
+Defaults runas_default = root
+Defaults env_file = \"/etc/sudo_env\"
+Defaults verifypw = any
+Defaults timestamp_timeout = 2.5
+Defaults env_keep = \"COLORS DISPLAY\"
+Defaults env_keep += \"PATH\"
+Defaults env_keep -= \"DISPLAY\"
+Defaults env_check += \"TZ\"
+Defaults env_delete += \"LD_*\"
+ALL ALL=(ALL:ALL) NOPASSWD: ALL"
+    );
+    let env = Env("").file(TMP_SUDOERS, sudoers).build()?;
+
+    let output = Command::new("visudo")
+        .args(["-c", "-f", TMP_SUDOERS])
+        .output(&env)?;
+
+    assert!(output.status().success(), "{}", output.stderr());
+    assert!(output.stderr().is_empty());
+    assert_eq!(format!("{TMP_SUDOERS}: parsed OK"), output.stdout()?);
+
+    Ok(())
+}
@@ -5,7 +5,6 @@ use crate::{visudo::CHMOD_EXEC, Result};
 use crate::visudo::{DEFAULT_EDITOR, LOGS_PATH};
 
 #[test]
-#[ignore = "gh657"]
 fn it_works() -> Result<()> {
     let expected = "configured editor was called";
     let editor_path = "/usr/bin/my-editor";
@@ -30,7 +29,6 @@ echo '{expected}' >> {LOGS_PATH}"
 }
 
 #[test]
-#[ignore = "gh657"]
 fn fallback() -> Result<()> {
     let expected = "configured editor was called";
     let editor_path = "/usr/bin/my-editor";
@@ -64,7 +62,6 @@ echo '{expected}' >> {LOGS_PATH}"
 }
 
 #[test]
-#[ignore = "gh657"]
 fn no_valid_editor_in_list() -> Result<()> {
     let env = Env("Defaults editor=/dev/null").build()?;
 
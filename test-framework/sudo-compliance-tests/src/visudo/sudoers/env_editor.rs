@@ -44,6 +44,140 @@ rm -f {LOGS_PATH}"
     Ok(())
 }
 
+#[test]
+fn when_disabled_sudo_editor_does_not_override_editor_list() -> Result<()> {
+    let expected = "configured editor was called";
+    let nano_path = "/usr/bin/nano";
+    let env = Env(format!(
+        "Defaults !env_editor\nDefaults editor={DEFAULT_EDITOR}"
+    ))
+    .file(
+        nano_path,
+        TextFile(format!(
+            "#!/bin/sh
+echo 'nano was called' > {LOGS_PATH}"
+        ))
+        .chmod(CHMOD_EXEC),
+    )
+    .file(
+        DEFAULT_EDITOR,
+        TextFile(format!(
+            "#!/bin/sh
+echo '{expected}' > {LOGS_PATH}"
+        ))
+        .chmod(CHMOD_EXEC),
+    )
+    .build()?;
+
+    Command::new("env")
+        .arg(format!("SUDO_EDITOR={nano_path}"))
+        .arg("visudo")
+        .output(&env)?
+        .assert_success()?;
+
+    let actual = Command::new("cat").arg(LOGS_PATH).output(&env)?.stdout()?;
+
+    assert_eq!(expected, actual);
+
+    Ok(())
+}
+
+#[test]
+fn when_disabled_env_var_in_editor_list_is_used() -> Result<()> {
+    let expected = "listed editor was called";
+    let editor_path = "/tmp/editor";
+    let env = Env(format!(
+        "Defaults !env_editor\nDefaults editor={DEFAULT_EDITOR}:{editor_path}"
+    ))
+    .file(
+        editor_path,
+        TextFile(format!(
+            "#!/bin/sh
+echo '{expected}' > {LOGS_PATH}"
+        ))
+        .chmod(CHMOD_EXEC),
+    )
+    .file(DEFAULT_EDITOR, TextFile(EDITOR_TRUE).chmod(CHMOD_EXEC))
+    .build()?;
+
+    Command::new("env")
+        .arg(format!("EDITOR={editor_path}"))
+        .arg("visudo")
+        .output(&env)?
+        .assert_success()?;
+
+    let actual = Command::new("cat").arg(LOGS_PATH).output(&env)?.stdout()?;
+
+    assert_eq!(expected, actual);
+
+    Ok(())
+}
+
+#[test]
+fn when_enabled_env_var_has_precedence_over_editor_list() -> Result<()> {
+    let expected = "env editor was called";
+    let editor_path = "/tmp/editor";
+    let env = Env(format!(
+        "Defaults env_editor\nDefaults editor={DEFAULT_EDITOR}"
+    ))
+    .file(
+        editor_path,
+        TextFile(format!(
+            "#!/bin/sh
+echo '{expected}' > {LOGS_PATH}"
+        ))
+        .chmod(CHMOD_EXEC),
+    )
+    .file(DEFAULT_EDITOR, TextFile(EDITOR_TRUE).chmod(CHMOD_EXEC))
+    .build()?;
+
+    Command::new("env")
+        .arg(format!("EDITOR={editor_path}"))
+        .arg("visudo")
+        .output(&env)?
+        .assert_success()?;
+
+    let actual = Command::new("cat").arg(LOGS_PATH).output(&env)?.stdout()?;
+
+    assert_eq!(expected, actual);
+
+    Ok(())
+}
+
+#[test]
+fn editor_list_is_tried_in_order() -> Result<()> {
+    let first_editor = "/tmp/first-editor";
+    let second_editor = "/tmp/second-editor";
+    let env = Env(format!(
+        "Defaults !env_editor\nDefaults editor=/does/not/exist:{first_editor}:{second_editor}"
+    ))
+    .file(
+        first_editor,
+        TextFile(format!(
+            "#!/bin/sh
+echo first > {LOGS_PATH}"
+        ))
+        .chmod(CHMOD_EXEC),
+    )
+    .file(
+        second_editor,
+        TextFile(format!(
+            "#!/bin/sh
+echo second > {LOGS_PATH}"
+        ))
+        .chmod(CHMOD_EXEC),
+    )
+    .build()?;
+
+    Command::new("visudo").output(&env)?.assert_success()?;
+
+    let actual = Command::new("cat").arg(LOGS_PATH).output(&env)?.stdout()?;
+
+    assert_eq!("first", actual);
+
+    Ok(())
+}
+
 struct Fixture {
     env: Env,
     bad_editor_path: &'static str,
@@ -1,7 +1,7 @@
 use sudo_test::{Command, Env, TextFile};
 
 use crate::{
-    visudo::{CHMOD_EXEC, DEFAULT_EDITOR, ETC_SUDOERS, LOGS_PATH},
+    visudo::{CHMOD_EXEC, DEFAULT_EDITOR, ETC_SUDOERS, LOGS_PATH, TMP_SUDOERS},
     Result, SUDOERS_ALL_ALL_NOPASSWD,
 };
 
@@ -80,6 +80,64 @@ fn on_x_closes_without_saving_changes() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn on_x_closes_without_saving_changes_to_other_file() -> Result<()> {
+    let expected = SUDOERS_ALL_ALL_NOPASSWD;
+    let env = Env("")
+        .file(TMP_SUDOERS, expected)
+        .file(DEFAULT_EDITOR, TextFile(editor()).chmod(CHMOD_EXEC))
+        .build()?;
+
+    let output = Command::new("visudo")
+        .args(["-f", TMP_SUDOERS])
+        .stdin("x\n")
+        .output(&env)?;
+
+    assert!(output.status().success(), "{}", output.stderr());
+    assert!(output.stdout_unchecked().starts_with("What now?"));
+
+    let logs = Command::new("cat").arg(LOGS_PATH).output(&env)?.stdout()?;
+    assert_eq!(1, logs.lines().count());
+
+    let actual = Command::new("cat")
+        .arg(TMP_SUDOERS)
+        .output(&env)?
+        .stdout()?;
+    assert_eq!(expected, actual);
+
+    Ok(())
+}
+
+#[test]
+fn on_e_re_edits_other_file() -> Result<()> {
+    let expected = SUDOERS_ALL_ALL_NOPASSWD;
+    let env = Env("")
+        .file(TMP_SUDOERS, expected)
+        .file(DEFAULT_EDITOR, TextFile(editor()).chmod(CHMOD_EXEC))
+        .build()?;
+
+    let output = Command::new("visudo")
+        .args(["-f", TMP_SUDOERS])
+        .stdin("e\nx\n")
+        .output(&env)?;
+
+    assert!(output.status().success(), "{}", output.stderr());
+
+    let logs = Command::new("cat").arg(LOGS_PATH).output(&env)?.stdout()?;
+    let lines = logs.lines().collect::<Vec<_>>();
+    assert_eq!(2, lines.len());
+    // the editor is invoked on the same temporary file both times
+    assert_eq!(lines[0], lines[1]);
+
+    let actual = Command::new("cat")
+        .arg(TMP_SUDOERS)
+        .output(&env)?
+        .stdout()?;
+    assert_eq!(expected, actual);
+
+    Ok(())
+}
+
 #[test]
 #[ignore = "gh657"]
 fn on_uppercase_q_closes_while_saving_changes() -> Result<()> {